@@ -0,0 +1,398 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{parse::ParseStream, spanned::Spanned, token::Semi, *};
+
+use crate::time::{is_time, Limit, Time, TimeUnit};
+
+/// A limit and the statement to run when a call exceeds it.
+#[derive(Clone)]
+pub struct Tier {
+    pub limit: Limit,
+    pub stmt: Option<Stmt>,
+}
+
+#[derive(Clone)]
+pub struct Args {
+    pub warn: Option<Tier>,
+    pub error: Option<Tier>,
+    pub fatal: Option<Tier>,
+    pub stmt: Option<Stmt>,
+    pub clock: TokenStream,
+    pub assert: bool,
+    pub guard: bool,
+    pub measure_self: bool,
+    pub on_ok_only: bool,
+    pub on_error_only: bool,
+    pub capture: Vec<Expr>,
+    pub max_per_sec: u64,
+    pub first: u64,
+    pub every: u64,
+    pub sample: (u64, u64),
+    pub sample_random: bool,
+    pub limit_per_item: Option<Limit>,
+    pub items: Option<Expr>,
+    pub complexity: TokenStream,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            warn: None,
+            error: None,
+            fatal: None,
+            stmt: None,
+            clock: quote! { ::slow_function_warning::DefaultClock },
+            assert: false,
+            guard: false,
+            measure_self: false,
+            on_ok_only: false,
+            on_error_only: false,
+            capture: Vec::new(),
+            max_per_sec: 0,
+            first: 0,
+            every: 0,
+            sample: (1, 1),
+            sample_random: false,
+            limit_per_item: None,
+            items: None,
+            complexity: quote! { Linear },
+        }
+    }
+}
+
+impl Args {
+    /// The tiers that have a limit, from the lowest to the highest severity,
+    /// with the name of their `Severity` variant.
+    pub fn tiers(&self) -> Vec<(&'static str, &Tier)> {
+        [
+            ("Warn", &self.warn),
+            ("Error", &self.error),
+            ("Fatal", &self.fatal),
+        ]
+        .into_iter()
+        .filter_map(|(severity, tier)| Some((severity, tier.as_ref()?)))
+        .collect()
+    }
+}
+
+fn parse_u64(expr: &Expr) -> Result<u64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(literal),
+            ..
+        }) if literal.suffix().is_empty() => literal.base10_parse::<u64>(),
+        _ => Err(Error::new(expr.span(), "Expected an integer literal")),
+    }
+}
+
+fn parse_fraction(expr: &Expr) -> Result<(u64, u64)> {
+    let Expr::Binary(ExprBinary {
+        left,
+        op: BinOp::Div(_),
+        right,
+        ..
+    }) = expr
+    else {
+        return Err(Error::new(expr.span(), "Expected a fraction such as 1/100"));
+    };
+    let numerator = parse_u64(left)?;
+    let denominator = parse_u64(right)?;
+    if numerator == 0 || numerator > denominator {
+        return Err(Error::new(
+            expr.span(),
+            "Expected a fraction between 0 (exclusive) and 1 (inclusive)",
+        ));
+    }
+    Ok((numerator, denominator))
+}
+
+fn parse_sampling(expr: &Expr) -> Result<bool> {
+    match expr {
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("counter") => Ok(false),
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("random") => Ok(true),
+        _ => Err(Error::new(expr.span(), "Expected `counter` or `random`")),
+    }
+}
+
+fn parse_clock(expr: &Expr) -> Result<TokenStream> {
+    let Expr::Path(ExprPath { path, .. }) = expr else {
+        return Err(Error::new(
+            expr.span(),
+            "Expected `std`, `tsc`, `mock` or the path of a type implementing `Clock`",
+        ));
+    };
+    if path.is_ident("std") {
+        Ok(quote! { ::slow_function_warning::StdClock })
+    } else if path.is_ident("tsc") {
+        Ok(quote! { ::slow_function_warning::TscClock })
+    } else if path.is_ident("mock") {
+        Ok(quote! { ::slow_function_warning::MockClock })
+    } else {
+        Ok(quote! { #path })
+    }
+}
+
+fn parse_mode(expr: &Expr) -> Result<bool> {
+    match expr {
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("warn") => Ok(false),
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("assert") => Ok(true),
+        _ => Err(Error::new(expr.span(), "Expected `warn` or `assert`")),
+    }
+}
+
+fn parse_strategy(expr: &Expr) -> Result<bool> {
+    match expr {
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("inline") => Ok(false),
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("guard") => Ok(true),
+        _ => Err(Error::new(expr.span(), "Expected `inline` or `guard`")),
+    }
+}
+
+/// Parses a tier given as a limit, or as a limit and a statement in
+/// parentheses.
+fn parse_tier(expr: &Expr) -> Result<Tier> {
+    match expr {
+        Expr::Tuple(ExprTuple { elems, .. }) if elems.len() == 2 => Ok(Tier {
+            limit: Limit::parse(&elems[0])?,
+            stmt: Some(Stmt::Expr(elems[1].clone(), Some(Semi::default()))),
+        }),
+        _ => Ok(Tier {
+            limit: Limit::parse(expr)?,
+            stmt: None,
+        }),
+    }
+}
+
+fn set_tier(tier: &mut Option<Tier>, expr: &Expr) -> Result<()> {
+    if tier.is_some() {
+        return Err(Error::new(
+            expr.span(),
+            "The limit of this tier is already set",
+        ));
+    }
+    *tier = Some(parse_tier(expr)?);
+    Ok(())
+}
+
+fn parse_complexity(expr: &Expr) -> Result<TokenStream> {
+    match expr {
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("n") => Ok(quote! { Linear }),
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("n_log_n") => Ok(quote! { NLogN }),
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("n_squared") => Ok(quote! { Quadratic }),
+        _ => Err(Error::new(
+            expr.span(),
+            "Expected `n`, `n_log_n` or `n_squared`",
+        )),
+    }
+}
+
+fn parse_measure(expr: &Expr) -> Result<bool> {
+    match expr {
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("total") => Ok(false),
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("self") => Ok(true),
+        _ => Err(Error::new(expr.span(), "Expected `total` or `self`")),
+    }
+}
+
+/// Parses comma separated expressions, joining literals that follow each other
+/// like `1s 500ms` into a sum.
+fn parse_exprs(input: ParseStream) -> Result<Vec<Expr>> {
+    let mut exprs = Vec::new();
+    while !input.is_empty() {
+        let mut expr: Expr = input.parse()?;
+        while input.peek(Lit) {
+            let next: Expr = input.parse()?;
+            expr = parse_quote!(#expr + #next);
+        }
+        exprs.push(expr);
+        if input.is_empty() {
+            break;
+        }
+        input.parse::<Token![,]>()?;
+    }
+    Ok(exprs)
+}
+
+pub fn parse_args(input: ParseStream) -> Result<Args> {
+    args_from_exprs(parse_exprs(input)?)
+}
+
+/// Whether a positional argument is a limit: a time, or the path of a
+/// `Duration` constant. Other expressions are statements, so a runtime limit
+/// needs `limit = ...`.
+fn is_positional_limit(expr: &Expr) -> bool {
+    is_time(expr) || matches!(expr, Expr::Path(_))
+}
+
+/// Parses the arguments of `slow_block!`, which are the arguments of the
+/// attribute with the name of the block after the time and the block or
+/// expression to time last.
+pub fn parse_block_args(input: ParseStream) -> Result<(Args, LitStr, Expr)> {
+    let mut args = parse_exprs(input)?;
+    let Some(body) = args.pop() else {
+        return Err(Error::new(
+            Span::call_site(),
+            "Expected a time, a name and a block",
+        ));
+    };
+    // The time can be left out when the limits are named.
+    let index = match (args.first(), args.get(1)) {
+        (
+            Some(time),
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Str(_), ..
+            })),
+        ) if is_positional_limit(time) => 1,
+        _ => 0,
+    };
+    let name = match args.get(index) {
+        Some(Expr::Lit(ExprLit {
+            lit: Lit::Str(name),
+            ..
+        })) => name.clone(),
+        Some(expr) => return Err(Error::new(expr.span(), "Expected a string literal name")),
+        None => {
+            return Err(Error::new(
+                body.span(),
+                "Expected a time, a name and a block",
+            ))
+        }
+    };
+    args.remove(index);
+    Ok((args_from_exprs(args)?, name, body))
+}
+
+fn args_from_exprs(args: impl IntoIterator<Item = Expr>) -> Result<Args> {
+    let mut result = Args::default();
+    let mut positional = 0;
+    for arg in args {
+        match arg {
+            Expr::Assign(assign) => {
+                let Expr::Path(ExprPath { path, .. }) = assign.left.as_ref() else {
+                    return Err(Error::new(assign.left.span(), "Expected an option name"));
+                };
+                let Some(name) = path.get_ident() else {
+                    return Err(Error::new(path.span(), "Expected an option name"));
+                };
+                match name.to_string().as_str() {
+                    "clock" => result.clock = parse_clock(&assign.right)?,
+                    "mode" => result.assert = parse_mode(&assign.right)?,
+                    "strategy" => result.guard = parse_strategy(&assign.right)?,
+                    "measure" => result.measure_self = parse_measure(&assign.right)?,
+                    "warn" | "limit" => set_tier(&mut result.warn, &assign.right)?,
+                    "error" => set_tier(&mut result.error, &assign.right)?,
+                    "fatal" => set_tier(&mut result.fatal, &assign.right)?,
+                    "limit_per_item" => result.limit_per_item = Some(Limit::parse(&assign.right)?),
+                    "items" => result.items = Some(*assign.right),
+                    "complexity" => result.complexity = parse_complexity(&assign.right)?,
+                    "max_per_sec" => result.max_per_sec = parse_u64(&assign.right)?,
+                    "first" => result.first = parse_u64(&assign.right)?,
+                    "every" => result.every = parse_u64(&assign.right)?,
+                    "sample" => result.sample = parse_fraction(&assign.right)?,
+                    "sampling" => result.sample_random = parse_sampling(&assign.right)?,
+                    option => {
+                        return Err(Error::new(
+                            name.span(),
+                            format!("Unknown option {}", option),
+                        ))
+                    }
+                }
+            }
+            Expr::Call(call) if matches!(call.func.as_ref(), Expr::Path(ExprPath { path, .. }) if path.is_ident("capture")) =>
+            {
+                result.capture.extend(call.args);
+            }
+            Expr::Path(ExprPath { path, .. }) if path.is_ident("on_ok_only") => {
+                result.on_ok_only = true;
+            }
+            Expr::Path(ExprPath { path, .. }) if path.is_ident("on_error_only") => {
+                result.on_error_only = true;
+            }
+            // The time can be left out when the tiers are named. A constant in
+            // its place is only a limit if no tier is named before it.
+            expr if positional == 0
+                && (is_time(&expr) || is_positional_limit(&expr) && result.tiers().is_empty()) =>
+            {
+                set_tier(&mut result.warn, &expr)?;
+                positional += 1;
+            }
+            expr if positional <= 1 && result.stmt.is_none() => {
+                result.stmt = Some(Stmt::Expr(expr, Some(Semi::default())));
+                positional = 2;
+            }
+            expr => return Err(Error::new(expr.span(), "Unexpected argument")),
+        }
+    }
+    // A limit per item becomes the `warn` limit, scaled by the number of items
+    // when the function is called.
+    match (&result.limit_per_item, result.items.take()) {
+        (Some(_), _) if result.warn.is_some() => {
+            return Err(Error::new(
+                Span::call_site(),
+                "`limit_per_item` can't be used with a `warn` limit",
+            ))
+        }
+        (Some(per_item), Some(items)) => {
+            let per_item = per_item.duration();
+            let complexity = &result.complexity;
+            result.warn = Some(Tier {
+                limit: Limit::Expr(parse_quote! {
+                    ::slow_function_warning::__private::Complexity::#complexity
+                        .scale(#per_item, (#items) as u64)
+                }),
+                stmt: None,
+            });
+        }
+        (Some(_), None) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "`limit_per_item` requires `items`",
+            ))
+        }
+        (None, Some(items)) => {
+            return Err(Error::new(
+                items.span(),
+                "`items` requires `limit_per_item`",
+            ))
+        }
+        (None, None) => {}
+    }
+    if result.warn.is_none() && result.error.is_none() && result.fatal.is_none() {
+        result.warn = Some(Tier {
+            limit: Limit::Time(Time {
+                nanos: 1_000_000,
+                unit: TimeUnit::Milliseconds,
+                decimals: 0,
+            }),
+            stmt: None,
+        });
+    }
+    // Limits evaluated at runtime can't be checked.
+    let limits: Vec<_> = result
+        .tiers()
+        .into_iter()
+        .filter_map(|(_, tier)| match &tier.limit {
+            Limit::Time(time) => Some(time.nanos),
+            Limit::Expr(_) => None,
+        })
+        .collect();
+    if limits.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(Error::new(
+            Span::call_site(),
+            "The limits of `warn`, `error` and `fatal` must increase",
+        ));
+    }
+    if result.on_ok_only && result.on_error_only {
+        return Err(Error::new(
+            Span::call_site(),
+            "`on_ok_only` and `on_error_only` can't be used together",
+        ));
+    }
+    if result.guard && (result.on_ok_only || result.on_error_only) {
+        return Err(Error::new(
+            Span::call_site(),
+            "`on_ok_only` and `on_error_only` can't be used with `strategy = guard`",
+        ));
+    }
+    Ok(result)
+}
@@ -0,0 +1,831 @@
+extern crate proc_macro;
+
+mod args;
+mod rewrite;
+mod time;
+
+use args::{parse_args, parse_block_args, Args};
+use rewrite::{InferRewriter, MacroExitFinder, ReturnRewriter};
+use time::{duration_str, Limit};
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{
+    parse_macro_input, punctuated::Punctuated, spanned::Spanned, visit::Visit, visit_mut::VisitMut,
+    *,
+};
+
+fn returns_result(ty: &Type) -> bool {
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return false;
+    };
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Result")
+}
+
+fn first_type_argument<'a>(ty: &'a Type, ident: &str) -> Option<&'a Type> {
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != ident {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+fn future_bound_output<'a>(
+    bounds: impl IntoIterator<Item = &'a TypeParamBound>,
+) -> Option<&'a Type> {
+    bounds.into_iter().find_map(|bound| {
+        let TypeParamBound::Trait(TraitBound { path, .. }) = bound else {
+            return None;
+        };
+        let segment = path.segments.last()?;
+        if segment.ident != "Future" {
+            return None;
+        }
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+        args.args.iter().find_map(|arg| match arg {
+            GenericArgument::AssocType(AssocType { ident, ty, .. }) if ident == "Output" => {
+                Some(ty)
+            }
+            _ => None,
+        })
+    })
+}
+
+/// Returns the output type of a future returned as `impl Future<Output = T>`,
+/// `Pin<Box<dyn Future<Output = T>>>` or the `BoxFuture<'a, T>` and
+/// `LocalBoxFuture<'a, T>` aliases of the `futures` crate, and whether the
+/// future is boxed.
+fn future_output(ty: &Type) -> Option<(&Type, bool)> {
+    if let Type::ImplTrait(TypeImplTrait { bounds, .. }) = ty {
+        return future_bound_output(bounds).map(|output| (output, false));
+    }
+    if let Some(output) =
+        first_type_argument(ty, "BoxFuture").or_else(|| first_type_argument(ty, "LocalBoxFuture"))
+    {
+        return Some((output, true));
+    }
+    let boxed = first_type_argument(first_type_argument(ty, "Pin")?, "Box")?;
+    let Type::TraitObject(TypeTraitObject { bounds, .. }) = boxed else {
+        return None;
+    };
+    future_bound_output(bounds).map(|output| (output, true))
+}
+
+/// Times the body as an expression. `return` and `?` are rewritten to break
+/// out of a labeled block, unless the body may return through a macro
+/// invocation. Then it is wrapped in a closure, or an async block in an
+/// `async fn`, so those returns are timed too.
+fn timed_body(
+    mut block: Box<Block>,
+    label: &Lifetime,
+    result_type: &TokenStream2,
+    is_async: bool,
+) -> TokenStream2 {
+    let mut finder = MacroExitFinder::default();
+    finder.visit_block(&block);
+    if finder.found {
+        if is_async {
+            quote! { async #block.await }
+        } else {
+            quote! { (|| -> #result_type #block)() }
+        }
+    } else {
+        ReturnRewriter {
+            label: label.clone(),
+        }
+        .visit_block_mut(&mut block);
+        quote! { #label: #block }
+    }
+}
+
+fn let_type(ty: &Type) -> TokenStream2 {
+    let mut ty = ty.clone();
+    InferRewriter.visit_type_mut(&mut ty);
+    ty.into_token_stream()
+}
+
+#[proc_macro_attribute]
+pub fn slow_function_warning(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args with parse_args);
+    expand(args, input)
+}
+
+#[proc_macro]
+pub fn slow_block(input: TokenStream) -> TokenStream {
+    let (args, name, body) = parse_macro_input!(input with parse_block_args);
+    // The block is expanded as the body of a function with an inferred return
+    // type, named after the block for its location.
+    let function = ItemFn {
+        attrs: vec![],
+        vis: Visibility::Inherited,
+        sig: parse_quote_spanned! {name.span()=> fn block() -> _ },
+        block: Box::new(Block {
+            brace_token: Default::default(),
+            stmts: vec![Stmt::Expr(body, None)],
+        }),
+    };
+    match expand_fn(args, function, Owner::Block(name)) {
+        Ok(item) => item.block.into_token_stream().into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_attribute]
+pub fn assert_fast(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut args = parse_macro_input!(args with parse_args);
+    args.assert = true;
+    expand(args, input)
+}
+
+/// What the instrumented function belongs to, used for `qualified_function`.
+enum Owner {
+    /// A function outside of an `impl` block, or a method the attribute was
+    /// applied to directly.
+    Unknown,
+    /// A method of an `impl` block the attribute was applied to.
+    Impl,
+    /// A method of a trait `impl` block the attribute was applied to.
+    TraitImpl(String),
+    /// A block timed by `slow_block!`, with the name it was given.
+    Block(LitStr),
+}
+
+fn expand(args: Args, input: TokenStream) -> TokenStream {
+    let result = match syn::parse(input) {
+        Ok(Item::Fn(function)) => expand_fn(args, function, Owner::Unknown).map(Item::Fn),
+        Ok(Item::Impl(item)) => expand_impl(args, item).map(Item::Impl),
+        Ok(item) => Err(syn::Error::new(
+            item.span(),
+            "slow_function_warning can only be used on functions and impl blocks",
+        )),
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok(item) => item.into_token_stream().into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Whether an attribute is one of ours, directly or inside `cfg_attr`.
+fn is_instrumenting(attr: &Attribute) -> bool {
+    let is_ours = |path: &Path| {
+        path.segments.last().is_some_and(|segment| {
+            segment.ident == "slow_function_warning" || segment.ident == "assert_fast"
+        })
+    };
+    if is_ours(attr.path()) {
+        return true;
+    }
+    if !attr.path().is_ident("cfg_attr") {
+        return false;
+    }
+    // The attributes follow the predicate.
+    attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+        .is_ok_and(|metas| metas.iter().skip(1).any(|meta| is_ours(meta.path())))
+}
+
+fn expand_impl(args: Args, mut item: ItemImpl) -> Result<ItemImpl> {
+    let trait_name = item
+        .trait_
+        .as_ref()
+        .map(|(_, path, _)| path.to_token_stream().to_string().replace(' ', ""));
+    for impl_item in &mut item.items {
+        let ImplItem::Fn(method) = impl_item else {
+            continue;
+        };
+        // Methods with their own attribute are expanded by it.
+        if method.attrs.iter().any(is_instrumenting) {
+            continue;
+        }
+        let owner = match &trait_name {
+            Some(trait_name) => Owner::TraitImpl(trait_name.clone()),
+            None => Owner::Impl,
+        };
+        let function = expand_fn(
+            args.clone(),
+            ItemFn {
+                attrs: method.attrs.clone(),
+                vis: method.vis.clone(),
+                sig: method.sig.clone(),
+                block: Box::new(method.block.clone()),
+            },
+            owner,
+        )?;
+        method.block = *function.block;
+    }
+    Ok(item)
+}
+
+fn expand_fn(args: Args, function: ItemFn, owner: Owner) -> Result<ItemFn> {
+    let tiers = args.tiers();
+    // Durations are formatted like the lowest limit.
+    let time = &tiers[0].1.limit;
+    let variants: Vec<Ident> = tiers
+        .iter()
+        .map(|(severity, _)| Ident::new(severity, Span::call_site()))
+        .collect();
+    // Limits given as expressions are evaluated when the function is called.
+    let mut limit_stmts = Vec::new();
+    let tier_limits: Vec<TokenStream2> = tiers
+        .iter()
+        .map(|(severity, tier)| match &tier.limit {
+            Limit::Time(_) => tier.limit.duration(),
+            Limit::Expr(expr) => {
+                let ident = Ident::new(
+                    &format!("{}_limit", severity.to_lowercase()),
+                    Span::mixed_site(),
+                );
+                limit_stmts.push(quote! {
+                    let #ident: ::std::time::Duration = #expr;
+                });
+                ident.into_token_stream()
+            }
+        })
+        .collect();
+    let lowest_limit = &tier_limits[0];
+
+    // `Location::caller()` is only the caller's location in `#[track_caller]`
+    // functions.
+    let track_caller = function
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("track_caller"));
+    let caller = Ident::new("caller", Span::mixed_site());
+    let (caller_stmt, caller_vars, caller_field) = if track_caller {
+        (
+            quote! { let #caller = ::std::panic::Location::caller(); },
+            quote! {
+                let caller = #caller;
+                let caller_file = caller.file();
+                let caller_line = caller.line();
+                let caller_column = caller.column();
+            },
+            quote! { Some(caller) },
+        )
+    } else {
+        (quote! {}, quote! {}, quote! { None })
+    };
+
+    let captured = Ident::new("captured", Span::mixed_site());
+    let capture_names = args
+        .capture
+        .iter()
+        .map(|expr| expr.to_token_stream().to_string());
+    let capture_exprs = args.capture.iter();
+    let capture = quote! {
+        let #captured = [#((#capture_names, format!("{:?}", #capture_exprs))),*];
+    };
+
+    let default_stmt = {
+        let function_str = if args.capture.is_empty() {
+            quote! { qualified_function.as_str() }
+        } else {
+            quote! {
+                &format!(
+                    "{qualified_function}({})",
+                    captured_args
+                        .iter()
+                        .map(|(name, value)| format!("{name} = {value}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
+        };
+        let caller_str = if track_caller {
+            quote! { message += &format!(", called from {caller}"); }
+        } else {
+            quote! {}
+        };
+        syn::parse::<Stmt>(
+            quote! {
+                {
+                    let function = #function_str;
+                    let mut message = format!("{severity}: {module}::{function}: ran for {elapsed_str} (");
+                    if self_elapsed < elapsed {
+                        message += &format!("self: {self_elapsed_str}, ");
+                    }
+                    message += &format!("limit: {limit_str}");
+                    if suppressed > 0 {
+                        message += &format!(", {suppressed} similar warnings suppressed");
+                    }
+                    message += &format!(") at {file}:{line}:{column}");
+                    #caller_str
+                    match &thread_name {
+                        Some(thread_name) => message += &format!(", on thread {thread_name}"),
+                        None => message += &format!(", on thread {thread_id:?}"),
+                    }
+                    message += &format!(", started {timestamp_str}");
+                    if !children.is_empty() {
+                        message += &format!("\n{}", call_tree.trim_end());
+                    }
+                    match severity {
+                        ::slow_function_warning::Severity::Warn => println!("{message}"),
+                        ::slow_function_warning::Severity::Error => eprintln!("{message}"),
+                        ::slow_function_warning::Severity::Fatal => panic!("{message}"),
+                    }
+                }
+            }
+            .into(),
+        )
+        .unwrap()
+    };
+
+    // Each tier runs its own statement, the shared one or the default message.
+    let tier_stmts: Vec<TokenStream2> = tiers
+        .iter()
+        .map(|(_, tier)| {
+            tier.stmt
+                .as_ref()
+                .or(args.stmt.as_ref())
+                .unwrap_or(&default_stmt)
+                .into_token_stream()
+        })
+        .collect();
+    let lowest_stmt = &tier_stmts[0];
+    let higher_variants = &variants[1..];
+    let higher_stmts = &tier_stmts[1..];
+    let stmt = quote! {
+        match severity {
+            #(::slow_function_warning::Severity::#higher_variants => { #higher_stmts })*
+            _ => { #lowest_stmt }
+        }
+    };
+
+    let stmt = if args.assert {
+        quote! {
+            if cfg!(test) || ::slow_function_warning::is_assert_enabled() {
+                panic!("{module}::{function}: ran for {elapsed_str} (limit: {limit_str})");
+            }
+            #stmt
+        }
+    } else {
+        stmt.into_token_stream()
+    };
+
+    let clock = &args.clock;
+    let max_per_sec = args.max_per_sec;
+    let first = args.first;
+    let every = args.every;
+
+    let function_name_ident = function.sig.ident.clone();
+    let in_impl = match owner {
+        Owner::Unknown => function.sig.receiver().is_some(),
+        Owner::Impl | Owner::TraitImpl(_) => true,
+        Owner::Block(_) => false,
+    };
+    // Each instantiation of a generic function is named by its type arguments.
+    let type_params: Vec<&Ident> = function
+        .sig
+        .generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect();
+    let generics = quote! {
+        [#(::std::any::type_name::<#type_params>()),*]
+    };
+    let generic_function = if type_params.is_empty() {
+        quote! { function.to_string() }
+    } else {
+        quote! {
+            format!(
+                "{}::<{}>",
+                function,
+                generics
+                    .iter()
+                    .map(|name| ::slow_function_warning::__private::short_type_name(name))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        }
+    };
+    // Returns and `?` in a block apply to the surrounding function, so they
+    // are left as they are.
+    let rewrite_returns = !matches!(owner, Owner::Block(_));
+    let qualified_function = match &owner {
+        Owner::TraitImpl(trait_name) => quote! {
+            format!(
+                "<{} as {}>::{}",
+                ::slow_function_warning::__private::short_type_name(::std::any::type_name::<Self>()),
+                #trait_name,
+                #generic_function,
+            )
+        },
+        _ if in_impl => quote! {
+            format!(
+                "{}::{}",
+                ::slow_function_warning::__private::short_type_name(::std::any::type_name::<Self>()),
+                #generic_function,
+            )
+        },
+        _ => generic_function,
+    };
+    // Spanned to the function name so the macros expand to its location.
+    let location = quote_spanned! {function_name_ident.span()=>
+        let file = file!();
+        let line = line!();
+        let column = column!();
+    };
+    let function_name = match &owner {
+        Owner::Block(name) => Lit::Str(name.clone()),
+        _ => Lit::Str(LitStr::new(
+            &function_name_ident.to_string(),
+            Span::call_site(),
+        )),
+    };
+
+    let mut item = ItemFn {
+        attrs: function.attrs.clone(),
+        vis: function.vis.clone(),
+        sig: function.sig.clone(),
+        block: Box::new(Block {
+            brace_token: function.block.brace_token,
+            stmts: vec![],
+        }),
+    };
+
+    // Not visible to the custom statement, which gets `return_value` instead.
+    let result = Ident::new("result", Span::mixed_site());
+    let return_type: Type = match &function.sig.output {
+        ReturnType::Default => parse_quote! { () },
+        ReturnType::Type(_, ty) => ty.as_ref().clone(),
+    };
+    let future = if function.sig.asyncness.is_none() {
+        future_output(&return_type)
+    } else {
+        None
+    };
+    // The guard's report is a closure created before the body runs, so a
+    // custom statement would move or borrow the parameters the body uses.
+    let custom_stmt = args
+        .stmt
+        .iter()
+        .chain(tiers.iter().filter_map(|(_, tier)| tier.stmt.as_ref()))
+        .next();
+    if let (true, Some(stmt)) = (args.guard, custom_stmt) {
+        return Err(syn::Error::new(
+            stmt.span(),
+            "`strategy = guard` can't be used with a custom statement, use `capture(...)` to include arguments in the default message",
+        ));
+    }
+    if future.is_some() && args.guard {
+        return Err(syn::Error::new(
+            function.sig.output.span(),
+            "`strategy = guard` can't be used with functions returning a future",
+        ));
+    }
+    let output_type = future.map_or(&return_type, |(output, _)| output);
+    let is_result = returns_result(output_type);
+    if (args.on_ok_only || args.on_error_only) && !is_result {
+        return Err(syn::Error::new(
+            function.sig.output.span(),
+            "`on_ok_only` and `on_error_only` require the function to return a `Result`",
+        ));
+    }
+    let outcome_filter = if args.on_ok_only {
+        quote! { && ::core::result::Result::is_ok(&#result) }
+    } else if args.on_error_only {
+        quote! { && ::core::result::Result::is_err(&#result) }
+    } else {
+        quote! {}
+    };
+    let (outcome, outcome_field) = if is_result && !args.guard {
+        (
+            quote! {
+                let outcome = match &#result {
+                    ::core::result::Result::Ok(_) => ::slow_function_warning::Outcome::Ok,
+                    ::core::result::Result::Err(_) => ::slow_function_warning::Outcome::Err,
+                };
+            },
+            quote! { Some(outcome) },
+        )
+    } else {
+        (quote! {}, quote! { None })
+    };
+    let label = Lifetime {
+        apostrophe: Span::mixed_site(),
+        ident: Ident::new("body", Span::mixed_site()),
+    };
+    let block = function.block;
+    let result_type = let_type(&return_type);
+
+    let start = Ident::new("start", Span::mixed_site());
+    // Read once before anything is timed, so disabled warnings cost no clock
+    // reads and no call stack updates.
+    let enabled = Ident::new("enabled", Span::mixed_site());
+    let enabled_stmt = quote! {
+        let #enabled = ::slow_function_warning::__private::is_enabled();
+    };
+    // Futures are polled interleaved with other code on the same thread, so
+    // only synchronous calls are tracked on the call stack.
+    let frame = Ident::new("frame", Span::mixed_site());
+    let children = Ident::new("children", Span::mixed_site());
+    let tracked = future.is_none() && function.sig.asyncness.is_none();
+    let (enter_frame, exit_frame, record) = if tracked {
+        (
+            quote! {
+                let #frame = #enabled.then(::slow_function_warning::__private::Frame::enter);
+            },
+            quote! {
+                let (self_elapsed, #children) = match #frame {
+                    Some(#frame) => #frame.exit(elapsed),
+                    None => (elapsed, ::std::vec::Vec::new()),
+                };
+            },
+            quote! {
+                ::slow_function_warning::__private::Frame::record(#function_name, elapsed, #children);
+            },
+        )
+    } else {
+        (
+            quote! {},
+            quote! {
+                let self_elapsed = elapsed;
+                let #children = ::std::vec::Vec::<::slow_function_warning::CallNode>::new();
+            },
+            quote! {},
+        )
+    };
+    let sampler = Ident::new("sampler", Span::mixed_site());
+    let rate_limiter = Ident::new("rate_limiter", Span::mixed_site());
+    let (sample_numerator, sample_denominator) = args.sample;
+    let sample_random = args.sample_random;
+    let sampled = sample_numerator < sample_denominator;
+    let now = quote! { <#clock as ::slow_function_warning::Clock>::now };
+    let start_stmt = if sampled {
+        quote! { let #start = (#enabled && #sampler.sample()).then(#now); }
+    } else {
+        quote! { let #start = #enabled.then(#now); }
+    };
+    let elapsed_stmt = quote! {
+        let elapsed = #start.map_or(
+            ::std::time::Duration::ZERO,
+            <#clock as ::slow_function_warning::Clock>::elapsed,
+        );
+    };
+    // Calls that weren't timed, because warnings are disabled or the call was
+    // skipped by sampling, aren't recorded as nested calls either. The guard
+    // doesn't report them at all.
+    let record = if tracked && !args.guard {
+        quote! {
+            if #start.is_some() {
+                #record
+            }
+        }
+    } else {
+        record
+    };
+    let return_value = if args.guard {
+        quote! {}
+    } else {
+        quote! { let return_value = &#result; }
+    };
+
+    let elapsed_str = duration_str(time, quote! { elapsed });
+    let self_elapsed_str = duration_str(time, quote! { self_elapsed });
+    let measured = if args.measure_self {
+        quote! { self_elapsed }
+    } else {
+        quote! { elapsed }
+    };
+    // The highest tier the call exceeded.
+    let lowest_variant = &variants[0];
+    let mut tier_select = quote! {
+        (::slow_function_warning::Severity::#lowest_variant, #lowest_limit)
+    };
+    for (variant, limit) in variants.iter().zip(&tier_limits).skip(1) {
+        tier_select = quote! {
+            if #measured > #limit {
+                (::slow_function_warning::Severity::#variant, #limit)
+            } else {
+                #tier_select
+            }
+        };
+    }
+    let limit_strs: Vec<TokenStream2> = tiers
+        .iter()
+        .map(|(_, tier)| duration_str(&tier.limit, quote! { limit }))
+        .collect();
+    let lowest_limit_str = &limit_strs[0];
+    let higher_limit_strs = &limit_strs[1..];
+    let limit_str = quote! {
+        match severity {
+            #(::slow_function_warning::Severity::#higher_variants => #higher_limit_strs,)*
+            _ => #lowest_limit_str,
+        }
+    };
+
+    let slow_branch = quote! {
+        if #measured > #lowest_limit
+            && ::slow_function_warning::__private::is_enabled()
+            && ::slow_function_warning::__private::is_module_enabled(module_path!())
+            #outcome_filter
+            && #rate_limiter.allow()
+        {
+            #return_value
+            let captured_args: Vec<(&'static str, String)> = Vec::from(#captured);
+            let suppressed = #rate_limiter.take_suppressed();
+            let sample_rate = #sampler.rate();
+            let sample_weight = #sampler.weight();
+
+            let module = module_path!();
+            let function = #function_name;
+            let generics: Vec<&'static str> = Vec::from(#generics);
+            let qualified_function = #qualified_function;
+            #location
+            #caller_vars
+            #outcome
+
+            let thread_name = ::std::thread::current().name().map(str::to_string);
+            let thread_id = ::std::thread::current().id();
+            let timestamp = ::slow_function_warning::__private::call_start(elapsed);
+            let timestamp_str = ::slow_function_warning::__private::format_timestamp(timestamp);
+
+            let elapsed_str = #elapsed_str;
+            let self_elapsed_str = #self_elapsed_str;
+            let children = #children.clone();
+            let call_tree = ::slow_function_warning::__private::format_call_tree(&children);
+            let elapsed_ns = elapsed.as_nanos();
+            let elapsed_nanos = elapsed_ns;
+            let elapsed_nanoseconds = elapsed_ns;
+            let elapsed_us = elapsed.as_micros();
+            let elapsed_micros = elapsed_us;
+            let elapsed_microseconds = elapsed_us;
+            let elapsed_ms = elapsed.as_millis();
+            let elapsed_millis = elapsed_ms;
+            let elapsed_milliseconds = elapsed_ms;
+            let elapsed_s = elapsed.as_secs();
+            let elapsed_secs = elapsed_s;
+            let elapsed_seconds = elapsed_s;
+            let elapsed_m = elapsed.as_secs() / 60;
+            let elapsed_min = elapsed_m;
+            let elapsed_minutes = elapsed_m;
+            let elapsed_h = elapsed.as_secs() / 60 / 60;
+            let elapsed_hours = elapsed_h;
+            let elapsed_d = elapsed.as_secs() / 60 / 60 / 24;
+            let elapsed_days = elapsed_d;
+
+            let (severity, limit) = #tier_select;
+            let effective_limit = #lowest_limit;
+            let limit_str = #limit_str;
+            let ratio = #measured.as_secs_f64() / limit.as_secs_f64();
+            let limit_ns = limit.as_nanos();
+            let limit_nanos = limit_ns;
+            let limit_nanoseconds = limit_ns;
+            let limit_us = limit.as_micros();
+            let limit_micros = limit_us;
+            let limit_microseconds = limit_us;
+            let limit_ms = limit.as_millis();
+            let limit_millis = limit_ms;
+            let limit_milliseconds = limit_ms;
+            let limit_s = limit.as_secs();
+            let limit_secs = limit_s;
+            let limit_seconds = limit_s;
+            let limit_m = limit.as_secs() / 60;
+            let limit_min = limit_m;
+            let limit_minutes = limit_m;
+            let limit_h = limit.as_secs() / 60 / 60;
+            let limit_hours = limit_h;
+            let limit_d = limit.as_secs() / 60 / 60 / 24;
+            let limit_days = limit_d;
+
+            if !::slow_function_warning::__private::report(|| ::slow_function_warning::__private::NewSlowCall {
+                module,
+                function: function.to_string(),
+                qualified_function: qualified_function.clone(),
+                generics: generics.clone(),
+                elapsed,
+                self_elapsed,
+                children: children.clone(),
+                limit,
+                severity,
+                timestamp,
+                thread_name: thread_name.clone(),
+                thread_id,
+                outcome: #outcome_field,
+                args: captured_args.clone(),
+                file,
+                line,
+                column,
+                caller: #caller_field,
+            }) {
+                #stmt
+            }
+        }
+    };
+
+    let statics = quote! {
+        #(#limit_stmts)*
+        #capture
+        #caller_stmt
+        let #sampler = {
+            static SAMPLER: ::slow_function_warning::__private::Sampler =
+                ::slow_function_warning::__private::Sampler::new(#sample_numerator, #sample_denominator, #sample_random);
+            &SAMPLER
+        };
+        let #rate_limiter = {
+            static RATE_LIMITER: ::slow_function_warning::__private::RateLimiter =
+                ::slow_function_warning::__private::RateLimiter::new(#max_per_sec, #first, #every);
+            &RATE_LIMITER
+        };
+    };
+
+    item.block = if args.guard {
+        let stmts = &block.stmts;
+        let guard = Ident::new("guard", Span::mixed_site());
+        syn::parse(
+            quote! {{
+                #statics
+                #enabled_stmt
+                #enter_frame
+                #start_stmt
+                let #guard = ::slow_function_warning::__private::Guard::<#clock, _>::new(
+                    #start,
+                    move |elapsed: ::std::time::Duration| {
+                        #exit_frame
+                        #slow_branch
+                        #record
+                    },
+                );
+                #(#stmts)*
+            }}
+            .into(),
+        )
+        .unwrap()
+    } else if let Some((output, boxed)) = future {
+        let body = if rewrite_returns {
+            timed_body(block, &label, &result_type, false)
+        } else {
+            quote! { #block }
+        };
+        let output_type = let_type(output);
+        let future = Ident::new("future", Span::mixed_site());
+        let timed = quote! {
+            async move {
+                #enabled_stmt
+                #start_stmt
+                let #result: #output_type = #future.await;
+                #elapsed_stmt
+                #exit_frame
+                #slow_branch
+                #result
+            }
+        };
+        let timed = if boxed {
+            quote! { ::std::boxed::Box::pin(#timed) }
+        } else {
+            timed
+        };
+        syn::parse(
+            quote! {{
+                #statics
+                let #future: #result_type = #body;
+                #timed
+            }}
+            .into(),
+        )
+        .unwrap()
+    } else {
+        let body = if rewrite_returns {
+            timed_body(
+                block,
+                &label,
+                &result_type,
+                function.sig.asyncness.is_some(),
+            )
+        } else {
+            quote! { #block }
+        };
+        syn::parse(
+            quote! {{
+                #statics
+                #enabled_stmt
+                #enter_frame
+                #start_stmt
+                let #result: #result_type = #body;
+                #elapsed_stmt
+                #exit_frame
+                #slow_branch
+                #record
+                #result
+            }}
+            .into(),
+        )
+        .unwrap()
+    };
+
+    Ok(item)
+}
@@ -0,0 +1,146 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use syn::{
+    parse_quote,
+    visit::{self, Visit},
+    visit_mut::{self, VisitMut},
+    *,
+};
+
+/// Rewrites `return` and `?` in a function body to break out of the labeled
+/// block the body is inlined into, so the function is still timed when it
+/// returns early.
+///
+/// Closures, async blocks and nested items have their own `return` scope and
+/// are left untouched. Neither are macro invocations, which syn can't look
+/// into.
+pub struct ReturnRewriter {
+    pub label: Lifetime,
+}
+
+impl VisitMut for ReturnRewriter {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Closure(_) | Expr::Async(_) => {}
+            Expr::Return(ExprReturn { expr: value, .. }) => {
+                if let Some(value) = value {
+                    self.visit_expr_mut(value);
+                }
+                let label = &self.label;
+                *expr = match value {
+                    // Parenthesized so a value starting with `::` isn't parsed as
+                    // another label.
+                    Some(value) => parse_quote!(break #label (#value)),
+                    None => parse_quote!(break #label ()),
+                };
+            }
+            Expr::Try(ExprTry { expr: value, .. }) => {
+                self.visit_expr_mut(value);
+                let label = &self.label;
+                *expr = parse_quote! {
+                    match ::slow_function_warning::__private::Branch::branch(#value) {
+                        ::core::ops::ControlFlow::Continue(value) => value,
+                        ::core::ops::ControlFlow::Break(residual) => {
+                            break #label (::slow_function_warning::__private::FromResidual::from_residual(residual))
+                        }
+                    }
+                };
+            }
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_item_mut(&mut self, _: &mut Item) {}
+}
+
+/// Standard macros that don't return from the surrounding function, unless
+/// their arguments do.
+const NON_RETURNING_MACROS: &[&str] = &[
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "cfg",
+    "column",
+    "concat",
+    "dbg",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "env",
+    "eprint",
+    "eprintln",
+    "file",
+    "format",
+    "format_args",
+    "line",
+    "matches",
+    "module_path",
+    "option_env",
+    "panic",
+    "print",
+    "println",
+    "stringify",
+    "todo",
+    "unimplemented",
+    "unreachable",
+    "vec",
+    "write",
+    "writeln",
+];
+
+fn contains_exit(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => ident == "return",
+        TokenTree::Punct(punct) => punct.as_char() == '?',
+        TokenTree::Group(group) => contains_exit(group.stream()),
+        TokenTree::Literal(_) => false,
+    })
+}
+
+/// Finds macro invocations that may return from the function, such as
+/// `ready!` or `anyhow::bail!`, which `ReturnRewriter` can't look into.
+///
+/// Like `ReturnRewriter`, closures, async blocks and nested items are
+/// skipped.
+#[derive(Default)]
+pub struct MacroExitFinder {
+    pub found: bool,
+}
+
+impl<'ast> Visit<'ast> for MacroExitFinder {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Closure(_) | Expr::Async(_) => {}
+            _ => visit::visit_expr(self, expr),
+        }
+    }
+
+    fn visit_item(&mut self, _: &'ast Item) {}
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let known = mac.path.segments.last().is_some_and(|segment| {
+            NON_RETURNING_MACROS
+                .iter()
+                .any(|name| segment.ident == name)
+        });
+        if !known || contains_exit(mac.tokens.clone()) {
+            self.found = true;
+        }
+    }
+}
+
+/// Replaces types that can't be written in a `let` statement, such as
+/// `impl Trait` and `!`, with `_`.
+pub struct InferRewriter;
+
+impl VisitMut for InferRewriter {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        match ty {
+            Type::ImplTrait(_) | Type::Never(_) => {
+                *ty = Type::Infer(TypeInfer {
+                    underscore_token: Token![_](Span::call_site()),
+                })
+            }
+            _ => visit_mut::visit_type_mut(self, ty),
+        }
+    }
+}
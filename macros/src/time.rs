@@ -0,0 +1,272 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{spanned::Spanned, BinOp, Error, Expr, ExprBinary, ExprLit, Lit, Result};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum TimeUnit {
+    Nanoseconds,
+    Microseconds,
+    Milliseconds,
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+}
+
+impl TimeUnit {
+    fn from_suffix(suffix: &str, span: Span) -> Result<Self> {
+        Ok(match suffix {
+            "ns" => TimeUnit::Nanoseconds,
+            "us" | "μs" => TimeUnit::Microseconds,
+            "ms" => TimeUnit::Milliseconds,
+            "s" => TimeUnit::Seconds,
+            "m" => TimeUnit::Minutes,
+            "h" => TimeUnit::Hours,
+            "d" => TimeUnit::Days,
+            "" => TimeUnit::Milliseconds,
+            suffix => {
+                return Err(Error::new(
+                    span,
+                    format!("Unexpected a numeric literal suffix {}", suffix),
+                ))
+            }
+        })
+    }
+
+    fn nanos(self) -> u128 {
+        match self {
+            TimeUnit::Nanoseconds => 1,
+            TimeUnit::Microseconds => 1_000,
+            TimeUnit::Milliseconds => 1_000_000,
+            TimeUnit::Seconds => 1_000_000_000,
+            TimeUnit::Minutes => 60 * 1_000_000_000,
+            TimeUnit::Hours => 60 * 60 * 1_000_000_000,
+            TimeUnit::Days => 24 * 60 * 60 * 1_000_000_000,
+        }
+    }
+}
+
+/// A duration given in the arguments, and how to format durations like it.
+#[derive(Clone)]
+pub(crate) struct Time {
+    pub nanos: u64,
+    /// The smallest unit the duration was given in.
+    pub unit: TimeUnit,
+    /// The number of decimals needed to show the duration in its unit.
+    pub decimals: usize,
+}
+
+/// A limit given as a duration literal, or as an expression evaluating to a
+/// `Duration` when the function is called.
+#[derive(Clone)]
+pub(crate) enum Limit {
+    Time(Time),
+    Expr(Expr),
+}
+
+impl Limit {
+    pub(crate) fn parse(expr: &Expr) -> Result<Self> {
+        if is_time(expr) {
+            parse_time(expr).map(Limit::Time)
+        } else {
+            Ok(Limit::Expr(expr.clone()))
+        }
+    }
+
+    /// An expression evaluating to the limit as a `Duration`.
+    pub(crate) fn duration(&self) -> TokenStream {
+        match self {
+            Limit::Time(time) => {
+                let nanos = time.nanos;
+                quote! { ::std::time::Duration::from_nanos(#nanos) }
+            }
+            Limit::Expr(expr) => quote! { #expr },
+        }
+    }
+}
+
+/// Whether an argument is a literal or a sum of literals, which are parsed as
+/// a time.
+pub(crate) fn is_time(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(_) => true,
+        Expr::Binary(ExprBinary {
+            left,
+            op: BinOp::Add(_),
+            right,
+            ..
+        }) => is_time(left) && is_time(right),
+        _ => false,
+    }
+}
+
+fn too_long(span: Span) -> Error {
+    Error::new(span, "The duration is too long")
+}
+
+/// Converts an amount like `1.5` of a unit to nanoseconds.
+fn part_nanos(digits: &str, unit: TimeUnit, span: Span) -> Result<u128> {
+    if digits.is_empty() || digits.contains(['e', 'E']) {
+        return Err(Error::new(span, "Expected a decimal number"));
+    }
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let parse = |digits: &str| {
+        if digits.is_empty() {
+            Ok(0)
+        } else if !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            Err(Error::new(span, "Expected a decimal number"))
+        } else {
+            digits.parse::<u128>().map_err(|_| too_long(span))
+        }
+    };
+    let integer_nanos = parse(integer)?
+        .checked_mul(unit.nanos())
+        .ok_or_else(|| too_long(span))?;
+    let fraction_nanos = parse(fraction)?
+        .checked_mul(unit.nanos())
+        .ok_or_else(|| too_long(span))?;
+    let scale = 10u128
+        .checked_pow(fraction.len() as u32)
+        .ok_or_else(|| Error::new(span, "The duration is more precise than a nanosecond"))?;
+    if fraction_nanos % scale != 0 {
+        return Err(Error::new(
+            span,
+            "The duration is more precise than a nanosecond",
+        ));
+    }
+    integer_nanos
+        .checked_add(fraction_nanos / scale)
+        .ok_or_else(|| too_long(span))
+}
+
+/// Parses a string duration like `"2m30s"` or `"1s 500ms"`.
+fn parse_str(value: &str, span: Span) -> Result<(u128, TimeUnit)> {
+    let mut total: Option<(u128, TimeUnit)> = None;
+    let mut rest = value.trim_start();
+    while !rest.is_empty() {
+        let digits_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '_'))
+            .unwrap_or(rest.len());
+        let (digits, after) = rest.split_at(digits_end);
+        let suffix_end = after
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(after.len());
+        let (suffix, after) = after.split_at(suffix_end);
+        if suffix.is_empty() {
+            return Err(Error::new(
+                span,
+                "Expected a unit after each number, e.g. \"2m30s\"",
+            ));
+        }
+        let unit = TimeUnit::from_suffix(suffix, span)?;
+        let nanos = part_nanos(&digits.replace('_', ""), unit, span)?;
+        total = Some(add(total, (nanos, unit), span)?);
+        rest = after.trim_start();
+    }
+    total.ok_or_else(|| Error::new(span, "Expected a duration, e.g. \"2m30s\""))
+}
+
+fn add(
+    total: Option<(u128, TimeUnit)>,
+    part: (u128, TimeUnit),
+    span: Span,
+) -> Result<(u128, TimeUnit)> {
+    match total {
+        None => Ok(part),
+        Some((nanos, unit)) => Ok((
+            nanos.checked_add(part.0).ok_or_else(|| too_long(span))?,
+            unit.min(part.1),
+        )),
+    }
+}
+
+fn parse_nanos(expr: &Expr) -> Result<(u128, TimeUnit)> {
+    match expr {
+        Expr::Lit(ExprLit { lit, .. }) => match lit {
+            Lit::Int(literal) => {
+                let unit = TimeUnit::from_suffix(literal.suffix(), expr.span())?;
+                Ok((
+                    part_nanos(literal.base10_digits(), unit, expr.span())?,
+                    unit,
+                ))
+            }
+            Lit::Float(literal) => {
+                let unit = TimeUnit::from_suffix(literal.suffix(), expr.span())?;
+                Ok((
+                    part_nanos(literal.base10_digits(), unit, expr.span())?,
+                    unit,
+                ))
+            }
+            Lit::Str(literal) => parse_str(&literal.value(), expr.span()),
+            _ => Err(Error::new(expr.span(), "Expected a numeric literal")),
+        },
+        // Compound durations like `1s 500ms` are parsed as `1s + 500ms`.
+        Expr::Binary(ExprBinary {
+            left,
+            op: BinOp::Add(_),
+            right,
+            ..
+        }) => add(Some(parse_nanos(left)?), parse_nanos(right)?, expr.span()),
+        _ => Err(Error::new(expr.span(), "Expected a numeric literal")),
+    }
+}
+
+pub(crate) fn parse_time(expr: &Expr) -> Result<Time> {
+    let (nanos, unit) = parse_nanos(expr)?;
+    let nanos = u64::try_from(nanos).map_err(|_| too_long(expr.span()))?;
+    let decimals = (0..=9)
+        .find(|&decimals| (u128::from(nanos) * 10u128.pow(decimals)) % unit.nanos() == 0)
+        .unwrap_or(9) as usize;
+    Ok(Time {
+        nanos,
+        unit,
+        decimals,
+    })
+}
+
+/// Formats the `Duration` in `duration` in the unit the limit was given in,
+/// or with `Debug` if the limit is evaluated at runtime.
+pub(crate) fn duration_str(limit: &Limit, duration: TokenStream) -> TokenStream {
+    let time = match limit {
+        Limit::Time(time) => time,
+        Limit::Expr(_) => return quote! { format!("{:?}", #duration) },
+    };
+    if time.decimals > 0 {
+        let decimals = time.decimals;
+        let (amount, suffix) = match time.unit {
+            TimeUnit::Nanoseconds => (quote! { #duration.as_nanos() as f64 }, "ns"),
+            TimeUnit::Microseconds => (quote! { #duration.as_secs_f64() * 1e6 }, "μs"),
+            TimeUnit::Milliseconds => (quote! { #duration.as_secs_f64() * 1e3 }, "ms"),
+            TimeUnit::Seconds => (quote! { #duration.as_secs_f64() }, "s"),
+            TimeUnit::Minutes => (quote! { #duration.as_secs_f64() / 60.0 }, "m"),
+            TimeUnit::Hours => (quote! { #duration.as_secs_f64() / 60.0 / 60.0 }, "h"),
+            TimeUnit::Days => (quote! { #duration.as_secs_f64() / 60.0 / 60.0 / 24.0 }, "d"),
+        };
+        return quote! {
+            format!("{:.*}{}", #decimals, #amount, #suffix)
+        };
+    }
+    match time.unit {
+        TimeUnit::Nanoseconds => quote! {
+            format!("{}ns", #duration.as_nanos())
+        },
+        TimeUnit::Microseconds => quote! {
+            format!("{}μs", #duration.as_micros())
+        },
+        TimeUnit::Milliseconds => quote! {
+            format!("{}ms", #duration.as_millis())
+        },
+        TimeUnit::Seconds => quote! {
+            format!("{}s", #duration.as_secs())
+        },
+        TimeUnit::Minutes => quote! {
+            format!("{}m", #duration.as_secs() / 60)
+        },
+        TimeUnit::Hours => quote! {
+            format!("{}h", #duration.as_secs() / 60 / 60)
+        },
+        TimeUnit::Days => quote! {
+            format!("{}d", #duration.as_secs() / 60 / 60 / 24)
+        },
+    }
+}
@@ -0,0 +1,128 @@
+use std::{cell::RefCell, fmt::Write, time::Duration};
+
+/// The nested instrumented calls of a function with the same name, merged
+/// into one node of the call tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallNode {
+    /// The name of the function.
+    pub function: &'static str,
+    /// How many times the function was called.
+    pub count: u64,
+    /// The total time the calls took.
+    pub elapsed: Duration,
+    /// The instrumented calls nested in the calls.
+    pub children: Vec<CallNode>,
+}
+
+/// Adds a call to a list of merged calls.
+fn merge(nodes: &mut Vec<CallNode>, node: CallNode) {
+    match nodes
+        .iter_mut()
+        .find(|existing| existing.function == node.function)
+    {
+        Some(existing) => {
+            existing.count += node.count;
+            existing.elapsed += node.elapsed;
+            for child in node.children {
+                merge(&mut existing.children, child);
+            }
+        }
+        None => nodes.push(node),
+    }
+}
+
+/// Formats the nested calls of a slow call as an indented tree, one line per
+/// function, e.g. `  load_level: 2 calls, 12ms`.
+pub fn format_call_tree(children: &[CallNode]) -> String {
+    fn format_level(output: &mut String, nodes: &[CallNode], depth: usize) {
+        for node in nodes {
+            let calls = if node.count == 1 { "call" } else { "calls" };
+            let _ = writeln!(
+                output,
+                "{:indent$}{}: {} {calls}, {:?}",
+                "",
+                node.function,
+                node.count,
+                node.elapsed,
+                indent = depth * 2,
+            );
+            format_level(output, &node.children, depth + 1);
+        }
+    }
+
+    let mut output = String::new();
+    format_level(&mut output, children, 1);
+    output
+}
+
+thread_local! {
+    /// The nested instrumented calls for each instrumented call running on
+    /// this thread.
+    static STACK: RefCell<Vec<Vec<CallNode>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// An instrumented call on the thread-local call stack, used to measure the
+/// time spent in the call itself excluding nested instrumented calls and to
+/// record the tree of nested calls.
+///
+/// The frame is removed from the stack when it is exited or dropped, so a
+/// panicking call doesn't leave it behind.
+pub struct Frame {
+    depth: usize,
+}
+
+impl Frame {
+    #[inline]
+    pub fn enter() -> Self {
+        let depth = STACK.with_borrow_mut(|stack| {
+            stack.push(Vec::new());
+            stack.len()
+        });
+        Self { depth }
+    }
+
+    /// Removes the frame from the stack and returns the time spent in this
+    /// call excluding its nested calls, and the nested calls.
+    #[inline]
+    pub fn exit(self, elapsed: Duration) -> (Duration, Vec<CallNode>) {
+        let children = STACK.with_borrow_mut(|stack| {
+            let children = stack.get_mut(self.depth - 1).map(std::mem::take);
+            stack.truncate(self.depth - 1);
+            children.unwrap_or_default()
+        });
+        std::mem::forget(self);
+        let nested = children.iter().map(|child| child.elapsed).sum();
+        (elapsed.saturating_sub(nested), children)
+    }
+
+    /// Records an exited call as a nested call of the calling instrumented
+    /// call, if any.
+    #[inline]
+    pub fn record(function: &'static str, elapsed: Duration, children: Vec<CallNode>) {
+        STACK.with_borrow_mut(|stack| {
+            if let Some(parent) = stack.last_mut() {
+                merge(
+                    parent,
+                    CallNode {
+                        function,
+                        count: 1,
+                        elapsed,
+                        children,
+                    },
+                );
+            }
+        });
+    }
+}
+
+impl Drop for Frame {
+    #[inline]
+    fn drop(&mut self) {
+        // Ignore the stack being destroyed while unwinding at thread exit.
+        let _ = STACK.try_with(|stack| {
+            if let Ok(mut stack) = stack.try_borrow_mut() {
+                stack.truncate(self.depth - 1);
+            }
+        });
+    }
+}
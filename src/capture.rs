@@ -0,0 +1,218 @@
+#[cfg(not(target_family = "wasm"))]
+use std::time::SystemTime;
+use std::{cell::RefCell, fmt, panic::Location, thread::ThreadId, time::Duration};
+
+#[cfg(target_family = "wasm")]
+use web_time::SystemTime;
+
+use crate::call_stack::CallNode;
+
+/// Whether a function returning a `Result` returned `Ok` or `Err`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    /// The function returned `Ok`.
+    Ok,
+    /// The function returned `Err`.
+    Err,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Ok => f.write_str("Ok"),
+            Outcome::Err => f.write_str("Err"),
+        }
+    }
+}
+
+/// How far over its limits a slow call was.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The call exceeded the `warn` limit, the default.
+    Warn,
+    /// The call exceeded the `error` limit.
+    Error,
+    /// The call exceeded the `fatal` limit.
+    Fatal,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warn => f.write_str("Warning"),
+            Severity::Error => f.write_str("Error"),
+            Severity::Fatal => f.write_str("Fatal"),
+        }
+    }
+}
+
+/// A call that took longer than its limit.
+///
+/// More fields may be added, so it can't be built or destructured outside
+/// this crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SlowCall {
+    /// The module the function is in.
+    pub module: &'static str,
+    /// The name of the function.
+    pub function: String,
+    /// The name of the function including the type and trait of the `impl`
+    /// block it is in, if known.
+    pub qualified_function: String,
+    /// The full names of the type arguments of a generic function.
+    pub generics: Vec<&'static str>,
+    /// How long the call took.
+    pub elapsed: Duration,
+    /// How long the call took excluding nested instrumented calls.
+    pub self_elapsed: Duration,
+    /// The instrumented calls nested in the call.
+    pub children: Vec<CallNode>,
+    /// The limit the call exceeded, the highest one if it has several.
+    pub limit: Duration,
+    /// The severity of the highest limit the call exceeded.
+    pub severity: Severity,
+    /// The wall-clock time the call started at, computed from the time the
+    /// call ended and `elapsed`.
+    pub timestamp: SystemTime,
+    /// The name of the thread the call ran on, if it has one.
+    pub thread_name: Option<String>,
+    /// The id of the thread the call ran on.
+    pub thread_id: ThreadId,
+    /// The outcome of the call if the function returns a `Result`.
+    pub outcome: Option<Outcome>,
+    /// The arguments selected with `capture(...)`, as names and `Debug`
+    /// formatted values.
+    pub args: Vec<(&'static str, String)>,
+    /// The file the function is defined in.
+    pub file: &'static str,
+    /// The line the function is defined on.
+    pub line: u32,
+    /// The column the function name starts at.
+    pub column: u32,
+    /// Where the function was called from, if it is `#[track_caller]`.
+    pub caller: Option<&'static Location<'static>>,
+}
+
+thread_local! {
+    static CAPTURES: RefCell<Vec<Vec<SlowCall>>> = const { RefCell::new(Vec::new()) };
+}
+
+struct CaptureGuard;
+
+impl Drop for CaptureGuard {
+    fn drop(&mut self) {
+        CAPTURES.with(|captures| captures.borrow_mut().pop());
+    }
+}
+
+/// Runs `f` and returns the slow calls made on the current thread while it
+/// ran, instead of reporting them.
+///
+/// Captures can be nested, in which case slow calls are only returned by the
+/// innermost one.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_function_warning::{capture, slow_function_warning, MockClock};
+///
+/// #[slow_function_warning(1ms, clock = mock)]
+/// fn slow() {
+///     MockClock::advance(Duration::from_millis(2));
+/// }
+///
+/// let calls = capture(|| slow());
+/// assert_eq!(calls.len(), 1);
+/// assert_eq!(calls[0].function, "slow");
+/// ```
+pub fn capture<F: FnOnce()>(f: F) -> Vec<SlowCall> {
+    CAPTURES.with(|captures| captures.borrow_mut().push(Vec::new()));
+    let guard = CaptureGuard;
+    f();
+    let calls = CAPTURES.with(|captures| std::mem::take(captures.borrow_mut().last_mut().unwrap()));
+    drop(guard);
+    calls
+}
+
+/// The fields of a [`SlowCall`], which the generated code can't build
+/// directly because it is `#[non_exhaustive]`.
+pub struct NewSlowCall {
+    pub module: &'static str,
+    pub function: String,
+    pub qualified_function: String,
+    pub generics: Vec<&'static str>,
+    pub elapsed: Duration,
+    pub self_elapsed: Duration,
+    pub children: Vec<CallNode>,
+    pub limit: Duration,
+    pub severity: Severity,
+    pub timestamp: SystemTime,
+    pub thread_name: Option<String>,
+    pub thread_id: ThreadId,
+    pub outcome: Option<Outcome>,
+    pub args: Vec<(&'static str, String)>,
+    pub file: &'static str,
+    pub line: u32,
+    pub column: u32,
+    pub caller: Option<&'static Location<'static>>,
+}
+
+impl From<NewSlowCall> for SlowCall {
+    fn from(call: NewSlowCall) -> Self {
+        let NewSlowCall {
+            module,
+            function,
+            qualified_function,
+            generics,
+            elapsed,
+            self_elapsed,
+            children,
+            limit,
+            severity,
+            timestamp,
+            thread_name,
+            thread_id,
+            outcome,
+            args,
+            file,
+            line,
+            column,
+            caller,
+        } = call;
+        SlowCall {
+            module,
+            function,
+            qualified_function,
+            generics,
+            elapsed,
+            self_elapsed,
+            children,
+            limit,
+            severity,
+            timestamp,
+            thread_name,
+            thread_id,
+            outcome,
+            args,
+            file,
+            line,
+            column,
+            caller,
+        }
+    }
+}
+
+/// Hands the slow call to the innermost active capture on this thread.
+///
+/// Returns `false` if there is none, in which case the call should be
+/// reported as usual.
+pub fn report(call: impl FnOnce() -> NewSlowCall) -> bool {
+    CAPTURES.with(|captures| match captures.borrow_mut().last_mut() {
+        Some(calls) => {
+            calls.push(call().into());
+            true
+        }
+        None => false,
+    })
+}
@@ -0,0 +1,184 @@
+#[cfg(not(target_family = "wasm"))]
+use std::time::Instant;
+use std::{cell::Cell, time::Duration};
+
+#[cfg(target_family = "wasm")]
+use web_time::Instant;
+
+/// A source of time used to measure how long a function ran for.
+pub trait Clock {
+    /// A point in time read from the clock.
+    type Instant: Copy;
+
+    /// Reads the current time.
+    fn now() -> Self::Instant;
+
+    /// Returns the time passed since `start`.
+    fn elapsed(start: Self::Instant) -> Duration;
+}
+
+/// The clock used when the attribute does not specify one.
+///
+/// This is [`TscClock`] when the `tsc` feature is enabled and [`StdClock`]
+/// otherwise.
+#[cfg(not(feature = "tsc"))]
+pub type DefaultClock = StdClock;
+
+/// The clock used when the attribute does not specify one.
+///
+/// This is [`TscClock`] when the `tsc` feature is enabled and [`StdClock`]
+/// otherwise.
+#[cfg(feature = "tsc")]
+pub type DefaultClock = TscClock;
+
+/// Clock backed by `std::time::Instant`, or `web_time::Instant` on wasm.
+pub struct StdClock;
+
+impl Clock for StdClock {
+    type Instant = Instant;
+
+    #[inline]
+    fn now() -> Instant {
+        Instant::now()
+    }
+
+    #[inline]
+    fn elapsed(start: Instant) -> Duration {
+        start.elapsed()
+    }
+}
+
+/// Clock reading the CPU time stamp counter.
+///
+/// The counter is calibrated against [`StdClock`] the first time a duration is
+/// measured. Reading it is cheaper than reading `Instant`, but it is only
+/// accurate on CPUs with an invariant time stamp counter. On targets other
+/// than `x86_64` this falls back to [`StdClock`].
+pub struct TscClock;
+
+#[cfg(target_arch = "x86_64")]
+mod tsc {
+    use std::{
+        arch::x86_64::{_mm_lfence, _rdtsc},
+        sync::OnceLock,
+        time::Duration,
+    };
+
+    use super::Instant;
+
+    #[inline]
+    pub fn start() -> u64 {
+        // SAFETY: `rdtsc` is available on every x86_64 CPU.
+        unsafe { _rdtsc() }
+    }
+
+    #[inline]
+    pub fn end() -> u64 {
+        // `rdtscp` would wait for the timed instructions too, but it is a
+        // separate CPU feature that some CPUs and hypervisors don't expose.
+        // SAFETY: `lfence` is part of SSE2 and `rdtsc` is available on every
+        // x86_64 CPU. `lfence` keeps `rdtsc` from running before the
+        // preceding instructions finished.
+        unsafe {
+            _mm_lfence();
+            _rdtsc()
+        }
+    }
+
+    fn nanos_per_tick() -> f64 {
+        static NANOS_PER_TICK: OnceLock<f64> = OnceLock::new();
+        *NANOS_PER_TICK.get_or_init(|| {
+            let instant = Instant::now();
+            let ticks = start();
+            while instant.elapsed() < Duration::from_millis(1) {}
+            let nanos = instant.elapsed().as_nanos() as f64;
+            let ticks = end().wrapping_sub(ticks).max(1) as f64;
+            nanos / ticks
+        })
+    }
+
+    pub fn to_duration(ticks: u64) -> Duration {
+        Duration::from_nanos((ticks as f64 * nanos_per_tick()) as u64)
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl Clock for TscClock {
+    type Instant = u64;
+
+    #[inline]
+    fn now() -> u64 {
+        tsc::start()
+    }
+
+    #[inline]
+    fn elapsed(start: u64) -> Duration {
+        tsc::to_duration(tsc::end().wrapping_sub(start))
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+impl Clock for TscClock {
+    type Instant = <StdClock as Clock>::Instant;
+
+    #[inline]
+    fn now() -> Self::Instant {
+        StdClock::now()
+    }
+
+    #[inline]
+    fn elapsed(start: Self::Instant) -> Duration {
+        StdClock::elapsed(start)
+    }
+}
+
+thread_local! {
+    static MOCK_NOW: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+}
+
+/// Clock that only moves when it is advanced manually.
+///
+/// Each thread has its own mock time, which makes tests of slow functions
+/// and their handlers deterministic.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_function_warning::{slow_function_warning, MockClock};
+///
+/// #[slow_function_warning(1ms, clock = mock, { *warned = true; })]
+/// fn slow(warned: &mut bool) {
+///     MockClock::advance(Duration::from_millis(2));
+/// }
+///
+/// let mut warned = false;
+/// slow(&mut warned);
+/// assert!(warned);
+/// ```
+pub struct MockClock;
+
+impl MockClock {
+    /// Moves the mock time of the current thread forward by `duration`.
+    pub fn advance(duration: Duration) {
+        MOCK_NOW.with(|now| now.set(now.get() + duration));
+    }
+
+    /// Resets the mock time of the current thread to zero.
+    pub fn reset() {
+        MOCK_NOW.with(|now| now.set(Duration::ZERO));
+    }
+}
+
+impl Clock for MockClock {
+    type Instant = Duration;
+
+    #[inline]
+    fn now() -> Duration {
+        MOCK_NOW.with(Cell::get)
+    }
+
+    #[inline]
+    fn elapsed(start: Duration) -> Duration {
+        Self::now().saturating_sub(start)
+    }
+}
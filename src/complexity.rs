@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+/// How the limit of a function grows with the number of items it processes,
+/// used by `limit_per_item`.
+#[derive(Clone, Copy)]
+pub enum Complexity {
+    Linear,
+    NLogN,
+    Quadratic,
+}
+
+impl Complexity {
+    /// Returns the limit for `items` items. Empty inputs count as one item,
+    /// so they don't get a zero limit, and the logarithm is at least 1, so
+    /// small inputs get at least the linear limit.
+    pub fn scale(self, per_item: Duration, items: u64) -> Duration {
+        let items = items.max(1);
+        let units = match self {
+            Complexity::Linear => u128::from(items),
+            Complexity::NLogN => u128::from(items) * u128::from(items.max(2).ilog2()),
+            Complexity::Quadratic => u128::from(items) * u128::from(items),
+        };
+        let nanos = per_item.as_nanos().saturating_mul(units);
+        Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+    }
+}
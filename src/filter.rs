@@ -0,0 +1,74 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    RwLock,
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+static ASSERT_ENABLED: AtomicBool = AtomicBool::new(false);
+static FILTERED: AtomicBool = AtomicBool::new(false);
+static MODULES: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Enables or disables all warnings at runtime.
+///
+/// Warnings are enabled by default.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether warnings are enabled at runtime.
+#[inline]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Makes functions using `mode = assert` or `#[assert_fast]` panic when they
+/// are slow, even outside of `cfg(test)`.
+///
+/// This is useful in integration tests, where the code under test is not
+/// compiled with `cfg(test)`.
+pub fn set_assert_enabled(enabled: bool) {
+    ASSERT_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether slow functions using `mode = assert` panic outside of
+/// `cfg(test)`.
+#[inline]
+pub fn is_assert_enabled() -> bool {
+    ASSERT_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Only report warnings from the given modules and their submodules.
+///
+/// Module paths are matched as printed by `module_path!()`, so
+/// `"my_game::physics"` matches `my_game::physics` and
+/// `my_game::physics::collision` but not `my_game::physics_debug`.
+/// Passing an empty list removes the filter.
+pub fn set_module_filter<I, S>(modules: I)
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let modules: Vec<String> = modules.into_iter().map(Into::into).collect();
+    let mut guard = MODULES.write().unwrap_or_else(|err| err.into_inner());
+    FILTERED.store(!modules.is_empty(), Ordering::Relaxed);
+    *guard = modules;
+}
+
+/// Removes the module filter so warnings are reported from every module.
+pub fn clear_module_filter() {
+    set_module_filter(Vec::<String>::new());
+}
+
+/// Returns whether warnings from `module` pass the module filter.
+pub fn is_module_enabled(module: &str) -> bool {
+    if !FILTERED.load(Ordering::Relaxed) {
+        return true;
+    }
+    let modules = MODULES.read().unwrap_or_else(|err| err.into_inner());
+    modules.is_empty()
+        || modules.iter().any(|filter| {
+            module
+                .strip_prefix(filter.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        })
+}
@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use crate::clock::Clock;
+
+/// Times a function from its creation until it is dropped, used by
+/// `strategy = guard`.
+///
+/// The report is not run if the function panicked.
+pub struct Guard<C: Clock, F: FnOnce(Duration)> {
+    start: Option<C::Instant>,
+    report: Option<F>,
+}
+
+impl<C: Clock, F: FnOnce(Duration)> Guard<C, F> {
+    #[inline]
+    pub fn new(start: Option<C::Instant>, report: F) -> Self {
+        Self {
+            start,
+            report: Some(report),
+        }
+    }
+}
+
+impl<C: Clock, F: FnOnce(Duration)> Drop for Guard<C, F> {
+    #[inline]
+    fn drop(&mut self) {
+        if let (Some(start), Some(report)) = (self.start, self.report.take()) {
+            let elapsed = C::elapsed(start);
+            if !std::thread::panicking() {
+                report(elapsed);
+            }
+        }
+    }
+}
@@ -0,0 +1,20 @@
+/// Removes the module paths from a type name as returned by
+/// `std::any::type_name`, e.g. `alloc::vec::Vec<my_game::Player>` becomes
+/// `Vec<Player>`.
+pub fn short_type_name(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut path_start = 0;
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            result.truncate(path_start);
+        } else if c.is_alphanumeric() || c == '_' {
+            result.push(c);
+        } else {
+            result.push(c);
+            path_start = result.len();
+        }
+    }
+    result
+}
@@ -0,0 +1,87 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    OnceLock,
+};
+
+use crate::clock::{Clock, StdClock};
+
+fn now_secs() -> u64 {
+    static EPOCH: OnceLock<<StdClock as Clock>::Instant> = OnceLock::new();
+    StdClock::elapsed(*EPOCH.get_or_init(StdClock::now)).as_secs()
+}
+
+/// Per call site limit on how often a warning is reported.
+///
+/// A warning is reported if it is one of the `first` warnings, or every
+/// `every`th warning after that, and no more than `max_per_sec` warnings were
+/// reported in the current second. A limit of `0` disables that check.
+pub struct RateLimiter {
+    max_per_sec: u64,
+    first: u64,
+    every: u64,
+    seen: AtomicU64,
+    window: AtomicU64,
+    window_count: AtomicU64,
+    suppressed: AtomicU64,
+}
+
+impl RateLimiter {
+    pub const fn new(max_per_sec: u64, first: u64, every: u64) -> Self {
+        Self {
+            max_per_sec,
+            first,
+            every,
+            seen: AtomicU64::new(0),
+            window: AtomicU64::new(0),
+            window_count: AtomicU64::new(0),
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    /// Records a warning and returns whether it should be reported.
+    pub fn allow(&self) -> bool {
+        if self.max_per_sec == 0 && self.first == 0 && self.every == 0 {
+            return true;
+        }
+        if !self.allow_count() || !self.allow_rate() {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        true
+    }
+
+    /// Returns the number of warnings suppressed since the last reported one.
+    pub fn take_suppressed(&self) -> u64 {
+        self.suppressed.swap(0, Ordering::Relaxed)
+    }
+
+    fn allow_count(&self) -> bool {
+        if self.first == 0 && self.every == 0 {
+            return true;
+        }
+        let seen = self.seen.fetch_add(1, Ordering::Relaxed);
+        if seen < self.first {
+            return true;
+        }
+        self.every != 0 && (seen - self.first + 1).is_multiple_of(self.every)
+    }
+
+    fn allow_rate(&self) -> bool {
+        if self.max_per_sec == 0 {
+            return true;
+        }
+        // Windows are offset by one so the initial value of `window` never
+        // matches the current second.
+        let now = now_secs() + 1;
+        let window = self.window.load(Ordering::Relaxed);
+        if window != now
+            && self
+                .window
+                .compare_exchange(window, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            self.window_count.store(0, Ordering::Relaxed);
+        }
+        self.window_count.fetch_add(1, Ordering::Relaxed) < self.max_per_sec
+    }
+}
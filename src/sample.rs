@@ -0,0 +1,63 @@
+use std::{
+    cell::Cell,
+    hash::{BuildHasher, RandomState},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+thread_local! {
+    static RNG_STATE: Cell<u64> = Cell::new(RandomState::new().hash_one(0u64) | 1);
+}
+
+/// xorshift64, good enough to pick calls to time.
+fn next_random() -> u64 {
+    RNG_STATE.with(|state| {
+        let mut x = state.get();
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        state.set(x);
+        x
+    })
+}
+
+/// Per call site selection of which calls are timed.
+///
+/// Times `numerator` out of every `denominator` calls, either by counting
+/// calls or by picking them at random.
+pub struct Sampler {
+    numerator: u64,
+    denominator: u64,
+    random: bool,
+    calls: AtomicU64,
+}
+
+impl Sampler {
+    pub const fn new(numerator: u64, denominator: u64, random: bool) -> Self {
+        Self {
+            numerator,
+            denominator,
+            random,
+            calls: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns whether the current call should be timed.
+    pub fn sample(&self) -> bool {
+        let value = if self.random {
+            next_random()
+        } else {
+            self.calls.fetch_add(1, Ordering::Relaxed)
+        };
+        value % self.denominator < self.numerator
+    }
+
+    /// The fraction of calls that are timed.
+    pub fn rate(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    /// The number of calls each timed call stands for.
+    pub fn weight(&self) -> f64 {
+        self.denominator as f64 / self.numerator as f64
+    }
+}
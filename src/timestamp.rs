@@ -0,0 +1,50 @@
+use std::time::Duration;
+#[cfg(not(target_family = "wasm"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(target_family = "wasm")]
+use web_time::{SystemTime, UNIX_EPOCH};
+
+/// Returns the wall-clock time a call that ran for `elapsed` started at.
+///
+/// The wall clock is only read once the call turned out to be slow, so calls
+/// that aren't slow don't pay for it. The result is only accurate if
+/// `elapsed` was measured in real time, which isn't the case with
+/// `MockClock` or custom clocks.
+pub fn call_start(elapsed: Duration) -> SystemTime {
+    let now = SystemTime::now();
+    now.checked_sub(elapsed).unwrap_or(now)
+}
+
+/// Formats a wall-clock time as an RFC 3339 UTC timestamp with milliseconds,
+/// e.g. `2024-05-01T12:30:05.123Z`.
+pub fn format_timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // Converts days since the epoch to a civil date, see
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        secs_of_day / 3_600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis(),
+    )
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+#[test]
+#[should_panic(expected = "assert::sleep: ran for 2ms (limit: 1ms)")]
+fn mode_assert() {
+    #[slow_function_warning(1ms, clock = mock, mode = assert)]
+    pub fn sleep(millis: u64) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    sleep(2);
+}
+
+#[test]
+fn mode_assert_fast_enough() {
+    #[slow_function_warning(10ms, clock = mock, mode = assert)]
+    pub fn sleep(millis: u64) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    sleep(2);
+}
+
+#[test]
+#[should_panic(expected = "assert::sleep: ran for 5ms (limit: 1ms)")]
+fn assert_fast() {
+    #[assert_fast(1ms, clock = mock)]
+    pub fn sleep(millis: u64) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    sleep(5);
+}
+
+#[test]
+fn assert_fast_enough() {
+    #[assert_fast(10ms, clock = mock)]
+    pub fn sleep(millis: u64) -> u64 {
+        MockClock::advance(Duration::from_millis(millis));
+        millis
+    }
+
+    assert_eq!(sleep(2), 2);
+}
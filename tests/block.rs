@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+fn sleep(millis: u64) {
+    MockClock::advance(Duration::from_millis(millis));
+}
+
+#[test]
+fn block_value() {
+    let calls = capture(|| {
+        let value = slow_block!(1ms, "decode", clock = mock, {
+            sleep(2);
+            "42".parse::<u32>()
+        });
+        assert_eq!(value, Ok(42));
+    });
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].module, "block");
+    assert_eq!(calls[0].function, "decode");
+    assert_eq!(calls[0].qualified_function, "decode");
+    assert_eq!(calls[0].elapsed, Duration::from_millis(2));
+    assert_eq!(calls[0].file, file!());
+    assert_eq!(calls[0].line, 12);
+}
+
+#[test]
+fn fast_block() {
+    let calls = capture(|| {
+        slow_block!(1ms, "decode", clock = mock, sleep(1));
+    });
+
+    assert!(calls.is_empty());
+}
+
+#[test]
+fn expression() {
+    let values = [3, 1, 2];
+    let calls = capture(|| {
+        let sum: u32 = slow_block!(
+            1ms,
+            "sum",
+            clock = mock,
+            values.iter().inspect(|_| sleep(1)).sum()
+        );
+        assert_eq!(sum, 6);
+    });
+
+    assert_eq!(calls[0].function, "sum");
+}
+
+#[test]
+fn custom_statement() {
+    let mut message = String::new();
+    slow_block!(
+        1ms,
+        "load",
+        clock = mock,
+        {
+            message = format!("{function} took {elapsed_str} and returned {return_value}");
+        },
+        {
+            sleep(2);
+            5
+        }
+    );
+
+    assert_eq!(message, "load took 2ms and returned 5");
+}
+
+#[test]
+fn question_mark_applies_to_function() {
+    fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+        let value = slow_block!(1ms, "parse", clock = mock, {
+            sleep(2);
+            input.parse::<u32>()?
+        });
+        Ok(value + 1)
+    }
+
+    assert_eq!(parse("1"), Ok(2));
+    assert!(parse("x").is_err());
+}
+
+#[test]
+fn nested_in_function() {
+    #[slow_function_warning(10ms, clock = mock)]
+    fn update() {
+        slow_block!(1ms, "physics", clock = mock, sleep(6));
+        sleep(5);
+    }
+
+    let calls = capture(update);
+
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[1].self_elapsed, Duration::from_millis(5));
+    assert_eq!(calls[1].children[0].function, "physics");
+}
+
+#[test]
+fn guard_strategy() {
+    let calls = capture(|| {
+        slow_block!(1ms, "guarded", clock = mock, strategy = guard, sleep(2));
+    });
+
+    assert_eq!(calls[0].function, "guarded");
+}
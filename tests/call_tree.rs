@@ -0,0 +1,152 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+fn sleep(millis: u64) {
+    MockClock::advance(Duration::from_millis(millis));
+}
+
+#[slow_function_warning(10ms, clock = mock)]
+fn load_mesh(millis: u64) {
+    sleep(millis);
+}
+
+#[slow_function_warning(10ms, clock = mock)]
+fn load_level() {
+    load_mesh(2);
+    load_mesh(3);
+}
+
+#[slow_function_warning(10ms, clock = mock)]
+fn spawn_enemies() {
+    sleep(1);
+}
+
+#[slow_function_warning(10ms, clock = mock)]
+fn update_world() {
+    load_level();
+    spawn_enemies();
+    load_level();
+    sleep(1);
+}
+
+#[test]
+fn call_tree() {
+    let calls = capture(update_world);
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].elapsed, Duration::from_millis(12));
+    assert_eq!(
+        calls[0].children,
+        vec![
+            CallNode {
+                function: "load_level",
+                count: 2,
+                elapsed: Duration::from_millis(10),
+                children: vec![CallNode {
+                    function: "load_mesh",
+                    count: 4,
+                    elapsed: Duration::from_millis(10),
+                    children: vec![],
+                }],
+            },
+            CallNode {
+                function: "spawn_enemies",
+                count: 1,
+                elapsed: Duration::from_millis(1),
+                children: vec![],
+            },
+        ]
+    );
+}
+
+#[test]
+fn leaf() {
+    #[slow_function_warning(10ms, clock = mock)]
+    fn update() {
+        load_mesh(20);
+    }
+
+    let calls = capture(update);
+
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].function, "load_mesh");
+    assert!(calls[0].children.is_empty());
+    assert_eq!(calls[1].children.len(), 1);
+}
+
+#[test]
+fn call_tree_variable() {
+    #[slow_function_warning(10ms, clock = mock, {*value = call_tree.clone();})]
+    fn update(value: &mut String) {
+        update_world();
+    }
+
+    let mut value = String::new();
+    update(&mut value);
+
+    assert_eq!(
+        value,
+        "  update_world: 1 call, 12ms\n    load_level: 2 calls, 10ms\n      load_mesh: 4 calls, 10ms\n    spawn_enemies: 1 call, 1ms\n"
+    );
+}
+
+#[test]
+fn interleaved_async() {
+    #[slow_function_warning(1ms, clock = mock)]
+    async fn first() {
+        tokio::task::yield_now().await;
+        sleep(2);
+        tokio::task::yield_now().await;
+    }
+
+    #[slow_function_warning(1ms, clock = mock)]
+    async fn second() {
+        sleep(3);
+        tokio::task::yield_now().await;
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let calls = capture(|| {
+        runtime.block_on(async {
+            tokio::join!(first(), second());
+        });
+    });
+
+    assert_eq!(calls.len(), 2);
+    for call in &calls {
+        assert!(call.children.is_empty());
+        assert_eq!(call.self_elapsed, call.elapsed);
+    }
+}
+
+#[test]
+fn sampled_children() {
+    #[slow_function_warning(10ms, clock = mock, sample = 1/2)]
+    fn child() {
+        sleep(1);
+    }
+
+    #[slow_function_warning(1ms, clock = mock)]
+    fn parent() {
+        for _ in 0..4 {
+            child();
+        }
+    }
+
+    let calls = capture(parent);
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].elapsed, Duration::from_millis(4));
+    assert_eq!(
+        calls[0].children,
+        vec![CallNode {
+            function: "child",
+            count: 2,
+            elapsed: Duration::from_millis(2),
+            children: vec![],
+        }]
+    );
+}
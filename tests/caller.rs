@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+#[slow_function_warning(1ms, clock = mock, {*value = (caller_file, caller_line);})]
+#[track_caller]
+fn sleep(millis: u64, value: &mut (&'static str, u32)) {
+    MockClock::advance(Duration::from_millis(millis));
+}
+
+#[test]
+fn caller_location() {
+    let mut value = ("", 0);
+    sleep(2, &mut value);
+    let line = line!() - 1;
+
+    assert_eq!(value, (file!(), line));
+}
+
+#[test]
+fn caller_location_per_call_site() {
+    let mut first = ("", 0);
+    let mut second = ("", 0);
+    sleep(2, &mut first);
+    sleep(2, &mut second);
+
+    assert_eq!(first.1 + 1, second.1);
+}
+
+#[test]
+fn caller_event() {
+    #[slow_function_warning(1ms, clock = mock)]
+    #[track_caller]
+    fn sleep(millis: u64) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let calls = capture(|| sleep(2));
+    let line = line!() - 1;
+
+    let caller = calls[0].caller.unwrap();
+    assert_eq!(caller.file(), file!());
+    assert_eq!(caller.line(), line);
+}
+
+#[test]
+fn caller_guard() {
+    #[slow_function_warning(1ms, clock = mock, strategy = guard)]
+    #[track_caller]
+    fn sleep(millis: u64) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let calls = capture(|| sleep(2));
+    let line = line!() - 1;
+
+    assert_eq!(calls[0].caller.unwrap().line(), line);
+}
+
+#[test]
+fn no_caller_without_track_caller() {
+    #[slow_function_warning(1ms, clock = mock)]
+    fn sleep(millis: u64) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let calls = capture(|| sleep(2));
+
+    assert_eq!(calls[0].caller, None);
+}
+
+#[test]
+fn caller_default_message() {
+    #[slow_function_warning(1ms, clock = mock)]
+    #[track_caller]
+    fn sleep(millis: u64) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    sleep(2);
+}
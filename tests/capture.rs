@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+#[slow_function_warning(1ms, clock = mock, {*warned = true;})]
+fn sleep(millis: u64, warned: &mut bool) {
+    MockClock::advance(Duration::from_millis(millis));
+}
+
+#[test]
+fn capture_slow_calls() {
+    let mut warned = false;
+    let calls = capture(|| {
+        sleep(2, &mut warned);
+        sleep(1, &mut warned);
+        sleep(3, &mut warned);
+    });
+
+    assert!(!warned);
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].elapsed, Duration::from_millis(2));
+    assert_eq!(calls[1].elapsed, Duration::from_millis(3));
+    for call in &calls {
+        assert_eq!(call.module, "capture");
+        assert_eq!(call.function, "sleep");
+        assert_eq!(call.self_elapsed, call.elapsed);
+        assert!(call.children.is_empty());
+        assert_eq!(call.limit, Duration::from_millis(1));
+        assert_eq!(call.severity, Severity::Warn);
+        assert_eq!(call.thread_id, std::thread::current().id());
+        assert_eq!(call.outcome, None);
+        assert_eq!(call.file, file!());
+        assert_eq!((call.line, call.column), (6, 4));
+    }
+}
+
+#[test]
+fn capture_nested() {
+    let mut warned = false;
+    let mut inner = vec![];
+    let outer = capture(|| {
+        sleep(2, &mut warned);
+        inner = capture(|| sleep(3, &mut warned));
+    });
+
+    assert!(!warned);
+    assert_eq!(outer.len(), 1);
+    assert_eq!(outer[0].elapsed, Duration::from_millis(2));
+    assert_eq!(inner.len(), 1);
+    assert_eq!(inner[0].elapsed, Duration::from_millis(3));
+}
+
+#[test]
+fn no_capture() {
+    let mut warned = false;
+    let calls = capture(|| {});
+    sleep(2, &mut warned);
+
+    assert!(calls.is_empty());
+    assert!(warned);
+}
@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+#[test]
+fn captured_args() {
+    #[slow_function_warning(1ms, clock = mock, capture(values, name), {*value = captured_args.clone();})]
+    pub fn sort(mut values: Vec<u32>, name: String, value: &mut Vec<(&'static str, String)>) {
+        MockClock::advance(Duration::from_millis(2));
+        values.sort();
+        drop(name);
+    }
+
+    let mut value = vec![];
+    sort(vec![3, 1, 2], "numbers".to_string(), &mut value);
+
+    assert_eq!(
+        value,
+        vec![
+            ("values", "[3, 1, 2]".to_string()),
+            ("name", "\"numbers\"".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn captured_expression() {
+    #[slow_function_warning(1ms, clock = mock, capture(values.len()), {*value = captured_args.clone();})]
+    pub fn sort(values: Vec<u32>, value: &mut Vec<(&'static str, String)>) {
+        MockClock::advance(Duration::from_millis(2));
+        drop(values);
+    }
+
+    let mut value = vec![];
+    sort(vec![3, 1, 2], &mut value);
+
+    assert_eq!(value, vec![("values.len()", "3".to_string())]);
+}
+
+#[test]
+#[should_panic(expected = "Fatal: capture_args::sort(values = [3, 1, 2]): ran for 2ms")]
+fn captured_args_default_message() {
+    // The default message of the `fatal` tier is the panic message.
+    #[slow_function_warning(fatal = 1ms, clock = mock, capture(values))]
+    pub fn sort(values: Vec<u32>) {
+        MockClock::advance(Duration::from_millis(2));
+        drop(values);
+    }
+
+    sort(vec![3, 1, 2]);
+}
+
+#[test]
+fn captured_args_event() {
+    #[slow_function_warning(1ms, clock = mock, capture(values))]
+    pub fn sort(values: Vec<u32>) {
+        MockClock::advance(Duration::from_millis(2));
+        drop(values);
+    }
+
+    let calls = capture(|| sort(vec![3, 1, 2]));
+
+    assert_eq!(calls[0].args, vec![("values", "[3, 1, 2]".to_string())]);
+}
@@ -0,0 +1,92 @@
+use std::{thread, time::Duration};
+
+use slow_function_warning::*;
+
+#[test]
+fn std_clock() {
+    #[slow_function_warning(1ms, clock = std, {*duration = elapsed;})]
+    pub fn sleep(millis: u64, duration: &mut Duration) {
+        thread::sleep(Duration::from_millis(millis));
+    }
+
+    let mut duration = Duration::default();
+    sleep(2, &mut duration);
+
+    assert!(duration >= Duration::from_millis(2));
+}
+
+#[test]
+fn tsc_clock() {
+    #[slow_function_warning(1ms, clock = tsc, {*duration = elapsed;})]
+    pub fn sleep(millis: u64, duration: &mut Duration) {
+        thread::sleep(Duration::from_millis(millis));
+    }
+
+    let mut duration = Duration::default();
+    sleep(5, &mut duration);
+
+    assert!(duration >= Duration::from_millis(4));
+    assert!(duration < Duration::from_millis(500));
+}
+
+#[test]
+fn tsc_clock_no_warn() {
+    #[slow_function_warning(50ms, clock = tsc, {*warned = true;})]
+    pub fn sleep(millis: u64, warned: &mut bool) {
+        thread::sleep(Duration::from_millis(millis));
+    }
+
+    let mut warned = false;
+    sleep(1, &mut warned);
+
+    assert!(!warned);
+}
+
+#[test]
+fn mock_clock() {
+    #[slow_function_warning(1ms, clock = mock, {*duration = elapsed;})]
+    pub fn sleep(millis: u64, duration: &mut Duration) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut duration = Duration::default();
+    sleep(3, &mut duration);
+
+    assert_eq!(duration, Duration::from_millis(3));
+}
+
+#[test]
+fn mock_clock_no_warn() {
+    #[slow_function_warning(1ms, clock = mock, {*warned = true;})]
+    pub fn sleep(warned: &mut bool) {
+        thread::sleep(Duration::from_millis(2));
+    }
+
+    let mut warned = false;
+    sleep(&mut warned);
+
+    assert!(!warned);
+}
+
+#[test]
+fn custom_clock() {
+    struct FixedClock;
+
+    impl Clock for FixedClock {
+        type Instant = ();
+
+        fn now() {}
+
+        fn elapsed(_: ()) -> Duration {
+            Duration::from_secs(1)
+        }
+    }
+
+    #[slow_function_warning(10ms, clock = FixedClock, {*duration = elapsed;})]
+    pub fn noop(duration: &mut Duration) {}
+
+    let mut duration = Duration::default();
+    noop(&mut duration);
+
+    assert_eq!(duration, Duration::from_secs(1));
+}
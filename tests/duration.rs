@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+macro_rules! limit_of {
+    ($($time:tt)*) => {{
+        #[slow_function_warning($($time)*, clock = mock, {*value = Some((limit, limit_str.clone(), elapsed_str.clone()));})]
+        fn update(value: &mut Option<(Duration, String, String)>) {
+            MockClock::advance(Duration::from_secs(100_000));
+        }
+
+        let mut value = None;
+        update(&mut value);
+        value.unwrap()
+    }};
+}
+
+fn limit(time: Duration, limit_str: &str) -> (Duration, String) {
+    (time, limit_str.to_string())
+}
+
+#[test]
+fn integer() {
+    let (time, limit_str, _) = limit_of!(5ms);
+    assert_eq!(
+        limit(time, &limit_str),
+        limit(Duration::from_millis(5), "5ms")
+    );
+}
+
+#[test]
+fn microseconds() {
+    let (time, limit_str, _) = limit_of!(5us);
+    assert_eq!(
+        limit(time, &limit_str),
+        limit(Duration::from_micros(5), "5μs")
+    );
+    let (time, limit_str, _) = limit_of!(5μs);
+    assert_eq!(
+        limit(time, &limit_str),
+        limit(Duration::from_micros(5), "5μs")
+    );
+}
+
+#[test]
+fn float() {
+    let (time, limit_str, elapsed_str) = limit_of!(16.6ms);
+    assert_eq!(
+        limit(time, &limit_str),
+        limit(Duration::from_micros(16_600), "16.6ms")
+    );
+    assert_eq!(elapsed_str, "100000000.0ms");
+
+    let (time, limit_str, _) = limit_of!(1.25s);
+    assert_eq!(
+        limit(time, &limit_str),
+        limit(Duration::from_millis(1250), "1.25s")
+    );
+
+    let (time, limit_str, _) = limit_of!(0.5m);
+    assert_eq!(
+        limit(time, &limit_str),
+        limit(Duration::from_secs(30), "0.5m")
+    );
+}
+
+#[test]
+fn compound() {
+    let (time, limit_str, _) = limit_of!(1s 500ms);
+    assert_eq!(
+        limit(time, &limit_str),
+        limit(Duration::from_millis(1500), "1500ms")
+    );
+
+    let (time, limit_str, _) = limit_of!(1h 30m 15s);
+    assert_eq!(
+        limit(time, &limit_str),
+        limit(Duration::from_secs(5415), "5415s")
+    );
+}
+
+#[test]
+fn string() {
+    let (time, limit_str, _) = limit_of!("2m30s");
+    assert_eq!(
+        limit(time, &limit_str),
+        limit(Duration::from_secs(150), "150s")
+    );
+
+    let (time, limit_str, _) = limit_of!("1s 500ms");
+    assert_eq!(
+        limit(time, &limit_str),
+        limit(Duration::from_millis(1500), "1500ms")
+    );
+
+    let (time, limit_str, _) = limit_of!("1.5ms");
+    assert_eq!(
+        limit(time, &limit_str),
+        limit(Duration::from_micros(1500), "1.5ms")
+    );
+}
+
+#[test]
+fn tiers() {
+    #[slow_function_warning(warn = 1.5ms, error = "2ms 500us", clock = mock, {*value = limit_str.clone();})]
+    fn update(millis: u64, value: &mut String) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut value = String::new();
+    update(2, &mut value);
+    assert_eq!(value, "1.5ms");
+
+    update(3, &mut value);
+    assert_eq!(value, "2500μs");
+}
+
+#[test]
+fn block() {
+    let calls = capture(|| {
+        slow_block!(1s 500ms, "load", clock = mock, {
+            MockClock::advance(Duration::from_secs(2));
+        });
+    });
+
+    assert_eq!(calls[0].limit, Duration::from_millis(1500));
+}
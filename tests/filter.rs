@@ -0,0 +1,123 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, MutexGuard,
+    },
+    time::Duration,
+};
+
+use slow_function_warning::*;
+
+// The switches are global, so tests touching them must not run concurrently.
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn lock() -> MutexGuard<'static, ()> {
+    let guard = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    set_enabled(true);
+    clear_module_filter();
+    guard
+}
+
+#[slow_function_warning(1ms, clock = mock, {*warned = true;})]
+fn sleep(millis: u64, warned: &mut bool) {
+    MockClock::advance(Duration::from_millis(millis));
+}
+
+mod physics {
+    use super::*;
+
+    #[slow_function_warning(1ms, clock = mock, {*warned = true;})]
+    pub fn sleep(millis: u64, warned: &mut bool) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+}
+
+#[test]
+fn disabled() {
+    let _guard = lock();
+    set_enabled(false);
+
+    let mut warned = false;
+    sleep(2, &mut warned);
+
+    set_enabled(true);
+    assert!(!warned);
+}
+
+#[test]
+fn disabled_skips_clock() {
+    static READS: AtomicUsize = AtomicUsize::new(0);
+
+    struct CountingClock;
+
+    impl Clock for CountingClock {
+        type Instant = ();
+
+        fn now() {
+            READS.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn elapsed(_: ()) -> Duration {
+            READS.fetch_add(1, Ordering::Relaxed);
+            Duration::ZERO
+        }
+    }
+
+    #[slow_function_warning(1ms, clock = CountingClock)]
+    fn noop() {}
+
+    #[slow_function_warning(1ms, clock = CountingClock, strategy = guard)]
+    fn guard() {}
+
+    let _guard = lock();
+    set_enabled(false);
+    noop();
+    guard();
+    set_enabled(true);
+
+    assert_eq!(READS.load(Ordering::Relaxed), 0);
+    noop();
+    guard();
+    assert_eq!(READS.load(Ordering::Relaxed), 4);
+}
+
+#[test]
+fn reenabled() {
+    let _guard = lock();
+    set_enabled(false);
+    set_enabled(true);
+
+    let mut warned = false;
+    sleep(2, &mut warned);
+
+    assert!(warned);
+}
+
+#[test]
+fn module_filter() {
+    let _guard = lock();
+    set_module_filter(["filter::physics"]);
+
+    let mut warned = false;
+    sleep(2, &mut warned);
+    let mut physics_warned = false;
+    physics::sleep(2, &mut physics_warned);
+
+    clear_module_filter();
+    assert!(!warned);
+    assert!(physics_warned);
+}
+
+#[test]
+fn module_filter_matches_submodules() {
+    let _guard = lock();
+    set_module_filter(["filter"]);
+
+    assert!(is_module_enabled("filter"));
+    assert!(is_module_enabled("filter::physics"));
+    assert!(!is_module_enabled("filter_physics"));
+    assert!(!is_module_enabled("other"));
+
+    clear_module_filter();
+    assert!(is_module_enabled("other"));
+}
@@ -0,0 +1,177 @@
+use std::{future::Future, pin::Pin, time::Duration};
+
+use futures::future::{BoxFuture, LocalBoxFuture};
+use slow_function_warning::*;
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+#[tokio::test]
+async fn impl_future() {
+    #[slow_function_warning(1ms, clock = mock, {*warned = true;})]
+    pub fn sleep(millis: u64, warned: &mut bool) -> impl Future<Output = u64> + '_ {
+        async move {
+            tokio::task::yield_now().await;
+            MockClock::advance(Duration::from_millis(millis));
+            millis
+        }
+    }
+
+    let mut warned = false;
+    assert_eq!(sleep(2, &mut warned).await, 2);
+
+    assert!(warned);
+}
+
+#[test]
+fn impl_future_times_await() {
+    #[slow_function_warning(1ms, clock = mock)]
+    pub fn sleep(millis: u64) -> impl Future<Output = u64> {
+        MockClock::advance(Duration::from_millis(millis));
+        async move {
+            MockClock::advance(Duration::from_millis(millis));
+            millis
+        }
+    }
+
+    let calls = capture(|| {
+        let future = sleep(2);
+        assert_eq!(block_on(future), 2);
+    });
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].elapsed, Duration::from_millis(2));
+}
+
+#[tokio::test]
+async fn no_warn_impl_future() {
+    #[slow_function_warning(50ms, clock = mock, {*warned = true;})]
+    pub fn sleep(millis: u64, warned: &mut bool) -> impl Future<Output = ()> + '_ {
+        async move {
+            tokio::task::yield_now().await;
+            MockClock::advance(Duration::from_millis(millis));
+        }
+    }
+
+    let mut warned = false;
+    sleep(1, &mut warned).await;
+
+    assert!(!warned);
+}
+
+#[tokio::test]
+async fn impl_future_outcome() {
+    #[slow_function_warning(1ms, clock = mock, on_error_only, {*warned = outcome == Outcome::Err;})]
+    pub fn sleep(
+        millis: u64,
+        warned: &mut bool,
+    ) -> impl Future<Output = Result<u64, String>> + Send + '_ {
+        async move {
+            tokio::task::yield_now().await;
+            MockClock::advance(Duration::from_millis(millis));
+            Err("failed".to_string())
+        }
+    }
+
+    let mut warned = false;
+    assert!(sleep(2, &mut warned).await.is_err());
+
+    assert!(warned);
+}
+
+#[test]
+fn boxed_future() {
+    trait Sleep {
+        fn sleep(&self, millis: u64) -> Pin<Box<dyn Future<Output = u64> + Send + '_>>;
+    }
+
+    struct Sleeper;
+
+    impl Sleep for Sleeper {
+        #[slow_function_warning(1ms, clock = mock)]
+        fn sleep(&self, millis: u64) -> Pin<Box<dyn Future<Output = u64> + Send + '_>> {
+            Box::pin(async move {
+                tokio::task::yield_now().await;
+                MockClock::advance(Duration::from_millis(millis));
+                millis
+            })
+        }
+    }
+
+    let mut value = 0;
+    let calls = capture(|| {
+        value = block_on(Sleeper.sleep(2));
+    });
+
+    assert_eq!(value, 2);
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].elapsed, Duration::from_millis(2));
+}
+
+#[test]
+fn box_future_alias() {
+    #[slow_function_warning(1ms, clock = mock)]
+    fn sleep(millis: u64) -> BoxFuture<'static, u64> {
+        Box::pin(async move {
+            tokio::task::yield_now().await;
+            MockClock::advance(Duration::from_millis(millis));
+            millis
+        })
+    }
+
+    #[slow_function_warning(1ms, clock = mock)]
+    fn sleep_local(millis: &u64) -> LocalBoxFuture<'_, u64> {
+        Box::pin(async move {
+            tokio::task::yield_now().await;
+            MockClock::advance(Duration::from_millis(*millis));
+            *millis
+        })
+    }
+
+    let calls = capture(|| {
+        assert_eq!(block_on(sleep(2)), 2);
+        assert_eq!(block_on(sleep_local(&3)), 3);
+    });
+
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].elapsed, Duration::from_millis(2));
+    assert_eq!(calls[1].elapsed, Duration::from_millis(3));
+}
+
+#[tokio::test]
+async fn async_fn_in_trait() {
+    trait Sleep {
+        async fn sleep(&mut self, millis: u64);
+
+        #[slow_function_warning(1ms, clock = mock, {*warned = true;})]
+        async fn sleep_default(&mut self, millis: u64, warned: &mut bool) {
+            tokio::task::yield_now().await;
+            MockClock::advance(Duration::from_millis(millis));
+        }
+    }
+
+    struct Sleeper {
+        warned: bool,
+    }
+
+    impl Sleep for Sleeper {
+        #[slow_function_warning(1ms, clock = mock, {self.warned = true;})]
+        async fn sleep(&mut self, millis: u64) {
+            tokio::task::yield_now().await;
+            MockClock::advance(Duration::from_millis(millis));
+        }
+    }
+
+    let mut sleeper = Sleeper { warned: false };
+    sleeper.sleep(2).await;
+    let mut warned = false;
+    sleeper.sleep_default(2, &mut warned).await;
+
+    assert!(sleeper.warned);
+    assert!(warned);
+}
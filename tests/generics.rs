@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+struct Transform;
+
+struct Name;
+
+#[slow_function_warning(1ms, clock = mock)]
+fn process<T>(millis: u64) {
+    MockClock::advance(Duration::from_millis(millis));
+}
+
+#[slow_function_warning(1ms, clock = mock)]
+fn convert<A, B: Default>(_value: A) -> B {
+    MockClock::advance(Duration::from_millis(2));
+    B::default()
+}
+
+struct Schedule;
+
+impl Schedule {
+    #[slow_function_warning(1ms, clock = mock)]
+    fn run<T>(&self) {
+        MockClock::advance(Duration::from_millis(2));
+    }
+}
+
+#[test]
+fn instantiations() {
+    let calls = capture(|| {
+        process::<Transform>(2);
+        process::<Name>(0);
+    });
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].function, "process");
+    assert_eq!(calls[0].qualified_function, "process::<Transform>");
+    assert_eq!(calls[0].generics, vec!["generics::Transform"]);
+}
+
+#[test]
+fn multiple_type_parameters() {
+    let calls = capture(|| {
+        let _: Vec<u8> = convert(Name);
+    });
+
+    assert_eq!(calls[0].qualified_function, "convert::<Name, Vec<u8>>");
+}
+
+#[test]
+fn generic_method() {
+    let calls = capture(|| Schedule.run::<Option<Transform>>());
+
+    assert_eq!(
+        calls[0].qualified_function,
+        "Schedule::run::<Option<Transform>>"
+    );
+}
+
+#[test]
+fn non_generic() {
+    #[slow_function_warning(1ms, clock = mock)]
+    fn update() {
+        MockClock::advance(Duration::from_millis(2));
+    }
+
+    let calls = capture(update);
+
+    assert!(calls[0].generics.is_empty());
+}
+
+#[test]
+fn generics_variable() {
+    #[slow_function_warning(1ms, clock = mock, {*value = generics.len();})]
+    fn update<T, U>(value: &mut usize) {
+        MockClock::advance(Duration::from_millis(2));
+    }
+
+    let mut value = 0;
+    update::<u8, u16>(&mut value);
+
+    assert_eq!(value, 2);
+}
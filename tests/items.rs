@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+fn sleep(nanos: u64) {
+    MockClock::advance(Duration::from_nanos(nanos));
+}
+
+#[slow_function_warning(limit_per_item = 50ns, items = values.len(), clock = mock)]
+fn sum(values: &[u64], nanos_per_item: u64) -> u64 {
+    sleep(values.len() as u64 * nanos_per_item);
+    values.iter().sum()
+}
+
+#[test]
+fn linear() {
+    let small = vec![1; 10];
+    let large = vec![1; 1000];
+    let calls = capture(|| {
+        sum(&small, 40);
+        sum(&large, 40);
+        sum(&small, 60);
+    });
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].limit, Duration::from_nanos(500));
+}
+
+#[test]
+fn empty() {
+    #[slow_function_warning(limit_per_item = 50ns, items = values.len(), clock = mock)]
+    fn sum(values: &[u64]) -> u64 {
+        sleep(10 + values.len() as u64 * 40);
+        values.iter().sum()
+    }
+
+    let calls = capture(|| {
+        sum(&[]);
+    });
+
+    assert!(calls.is_empty());
+}
+
+#[test]
+fn consumed_argument() {
+    #[slow_function_warning(limit_per_item = 50ns, items = values.len(), clock = mock)]
+    fn sort(mut values: Vec<u64>) -> Vec<u64> {
+        sleep(values.len() as u64 * 60);
+        values.sort();
+        values
+    }
+
+    let calls = capture(|| {
+        sort(vec![3, 1, 2]);
+    });
+
+    assert_eq!(calls[0].limit, Duration::from_nanos(150));
+}
+
+#[test]
+fn n_log_n() {
+    #[slow_function_warning(limit_per_item = 10ns, items = values.len(), complexity = n_log_n, clock = mock, {*value = Some(effective_limit);})]
+    fn sort(values: &mut [u64], value: &mut Option<Duration>) {
+        sleep(values.len() as u64 * values.len() as u64 * 4);
+        values.sort();
+    }
+
+    let mut value = None;
+    sort(&mut [0; 8], &mut value);
+    assert_eq!(value, Some(Duration::from_nanos(8 * 3 * 10)));
+
+    value = None;
+    sort(&mut [0; 4], &mut value);
+    assert_eq!(value, None);
+
+    sort(&mut [0; 1], &mut value);
+    assert_eq!(value, None);
+}
+
+#[test]
+fn n_squared() {
+    #[slow_function_warning(limit_per_item = 1ns, items = n, complexity = n_squared, clock = mock)]
+    fn pairs(n: u32) {
+        sleep(u64::from(n) * u64::from(n) + 1);
+    }
+
+    let calls = capture(|| pairs(30));
+
+    assert_eq!(calls[0].limit, Duration::from_nanos(900));
+}
+
+#[test]
+fn with_error_tier() {
+    #[slow_function_warning(limit_per_item = 1us, items = n, error = 1ms, clock = mock)]
+    fn process(n: u64, nanos: u64) {
+        sleep(nanos);
+    }
+
+    let calls = capture(|| {
+        process(10, 20_000);
+        process(10, 2_000_000);
+    });
+
+    assert_eq!(calls[0].severity, Severity::Warn);
+    assert_eq!(calls[0].limit, Duration::from_micros(10));
+    assert_eq!(calls[1].severity, Severity::Error);
+}
+
+#[test]
+fn effective_limit_of_fixed_limit() {
+    #[slow_function_warning(1ms, error = 10ms, clock = mock, {*value = Some(effective_limit);})]
+    fn update(value: &mut Option<Duration>) {
+        sleep(20_000_000);
+    }
+
+    let mut value = None;
+    update(&mut value);
+
+    assert_eq!(value, Some(Duration::from_millis(1)));
+}
@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+#[test]
+fn outcome() {
+    #[slow_function_warning(1ms, clock = mock, {*value = Some(outcome);})]
+    pub fn sleep(millis: u64, fail: bool, value: &mut Option<Outcome>) -> Result<(), String> {
+        MockClock::advance(Duration::from_millis(millis));
+        if fail {
+            Err("failed".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    let mut value = None;
+    assert!(sleep(2, false, &mut value).is_ok());
+    assert_eq!(value, Some(Outcome::Ok));
+
+    let mut value = None;
+    assert!(sleep(2, true, &mut value).is_err());
+    assert_eq!(value, Some(Outcome::Err));
+}
+
+#[test]
+fn outcome_result_alias() {
+    #[slow_function_warning(1ms, clock = mock, {*value = outcome.to_string();})]
+    pub fn sleep(millis: u64, value: &mut String) -> std::io::Result<u64> {
+        MockClock::advance(Duration::from_millis(millis));
+        Ok(millis)
+    }
+
+    let mut value = String::new();
+    assert_eq!(sleep(2, &mut value).unwrap(), 2);
+    assert_eq!(value, "Ok");
+}
+
+#[test]
+fn on_error_only() {
+    #[slow_function_warning(1ms, clock = mock, on_error_only, {*warned = true;})]
+    pub fn sleep(millis: u64, fail: bool, warned: &mut bool) -> Result<(), ()> {
+        MockClock::advance(Duration::from_millis(millis));
+        if fail {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    let mut warned = false;
+    let _ = sleep(2, false, &mut warned);
+    assert!(!warned);
+
+    let _ = sleep(2, true, &mut warned);
+    assert!(warned);
+}
+
+#[test]
+fn on_ok_only() {
+    #[slow_function_warning(1ms, clock = mock, on_ok_only, {*warned = true;})]
+    pub fn sleep(millis: u64, fail: bool, warned: &mut bool) -> Result<(), ()> {
+        MockClock::advance(Duration::from_millis(millis));
+        if fail {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    let mut warned = false;
+    let _ = sleep(2, true, &mut warned);
+    assert!(!warned);
+
+    let _ = sleep(2, false, &mut warned);
+    assert!(warned);
+}
+
+#[test]
+fn capture_outcome() {
+    #[slow_function_warning(1ms, clock = mock)]
+    pub fn sleep(millis: u64) -> Result<(), ()> {
+        MockClock::advance(Duration::from_millis(millis));
+        Err(())
+    }
+
+    let calls = capture(|| {
+        let _ = sleep(2);
+    });
+
+    assert_eq!(calls[0].outcome, Some(Outcome::Err));
+}
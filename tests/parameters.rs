@@ -0,0 +1,226 @@
+use std::{
+    task::{ready, Poll},
+    time::Duration,
+};
+
+use slow_function_warning::*;
+
+#[test]
+fn owned() {
+    #[slow_function_warning(1ms, clock = mock, {*value = values.len();})]
+    pub fn sort(mut values: Vec<u32>, value: &mut usize) {
+        MockClock::advance(Duration::from_millis(2));
+        values.sort();
+    }
+
+    let mut value = 0;
+    sort(vec![3, 1, 2], &mut value);
+
+    assert_eq!(value, 3);
+}
+
+#[test]
+fn borrowed() {
+    #[slow_function_warning(1ms, clock = mock, {*value = values.len();})]
+    pub fn sum(values: &[u32], value: &mut usize) -> u32 {
+        MockClock::advance(Duration::from_millis(2));
+        values.iter().sum()
+    }
+
+    let mut value = 0;
+    let values = vec![3, 1, 2];
+
+    assert_eq!(sum(&values, &mut value), 6);
+    assert_eq!(value, 3);
+}
+
+#[test]
+fn mutable() {
+    #[slow_function_warning(1ms, clock = mock, {*value = values.to_vec();})]
+    pub fn sort(values: &mut [u32], value: &mut Vec<u32>) {
+        MockClock::advance(Duration::from_millis(2));
+        values.sort();
+    }
+
+    let mut value = vec![];
+    let mut values = vec![3, 1, 2];
+    sort(&mut values, &mut value);
+
+    assert_eq!(values, vec![1, 2, 3]);
+    assert_eq!(value, vec![1, 2, 3]);
+}
+
+#[test]
+fn mutable_owned() {
+    #[slow_function_warning(1ms, clock = mock, {*value = values.clone();})]
+    pub fn sort(mut values: Vec<u32>, value: &mut Vec<u32>) -> usize {
+        MockClock::advance(Duration::from_millis(2));
+        values.sort();
+        values.len()
+    }
+
+    let mut value = vec![];
+
+    assert_eq!(sort(vec![3, 1, 2], &mut value), 3);
+    assert_eq!(value, vec![1, 2, 3]);
+}
+
+#[test]
+fn early_return() {
+    #[slow_function_warning(1ms, clock = mock, {*warned = true;})]
+    pub fn sleep(millis: u64, warned: &mut bool) -> u64 {
+        MockClock::advance(Duration::from_millis(millis));
+        if millis > 1 {
+            return millis * 2;
+        }
+        millis
+    }
+
+    let mut warned = false;
+
+    assert_eq!(sleep(2, &mut warned), 4);
+    assert!(warned);
+}
+
+#[test]
+fn question_mark() {
+    #[slow_function_warning(1ms, clock = mock, {*warned = outcome == Outcome::Err;})]
+    pub fn parse(value: &str, warned: &mut bool) -> Result<u32, Box<dyn std::error::Error>> {
+        MockClock::advance(Duration::from_millis(2));
+        let value = value.parse::<u32>()?;
+        Ok(value)
+    }
+
+    let mut warned = false;
+
+    assert!(parse("nope", &mut warned).is_err());
+    assert!(warned);
+    assert_eq!(parse("12", &mut warned).unwrap(), 12);
+    assert!(!warned);
+}
+
+#[test]
+fn question_mark_option() {
+    #[slow_function_warning(1ms, clock = mock, {*warned = true;})]
+    pub fn first(values: &[u32], warned: &mut bool) -> Option<u32> {
+        MockClock::advance(Duration::from_millis(2));
+        let value = values.first()?;
+        Some(*value)
+    }
+
+    let mut warned = false;
+
+    assert_eq!(first(&[], &mut warned), None);
+    assert!(warned);
+}
+
+#[test]
+fn question_mark_poll() {
+    #[slow_function_warning(1ms, clock = mock)]
+    pub fn poll_value(poll: Poll<Result<u32, String>>) -> Poll<Result<u32, String>> {
+        MockClock::advance(Duration::from_millis(2));
+        let value = ready!(poll)?;
+        Poll::Ready(Ok(value + 1))
+    }
+
+    let calls = capture(|| {
+        assert_eq!(poll_value(Poll::Ready(Ok(1))), Poll::Ready(Ok(2)));
+        assert_eq!(poll_value(Poll::Pending), Poll::Pending);
+        assert_eq!(
+            poll_value(Poll::Ready(Err("failed".to_string()))),
+            Poll::Ready(Err("failed".to_string()))
+        );
+    });
+
+    // `ready!` returns `Poll::Pending` from inside the macro.
+    assert_eq!(calls.len(), 3);
+}
+
+#[test]
+fn question_mark_poll_option() {
+    #[slow_function_warning(1ms, clock = mock)]
+    pub fn poll_next(poll: Poll<Option<Result<u32, String>>>) -> Poll<Option<Result<u32, String>>> {
+        MockClock::advance(Duration::from_millis(2));
+        let value = ready!(poll?);
+        Poll::Ready(value.map(|value| Ok(value + 1)))
+    }
+
+    let calls = capture(|| {
+        assert_eq!(
+            poll_next(Poll::Ready(Some(Ok(1)))),
+            Poll::Ready(Some(Ok(2)))
+        );
+        assert_eq!(poll_next(Poll::Ready(None)), Poll::Ready(None));
+        assert_eq!(poll_next(Poll::Pending), Poll::Pending);
+        assert_eq!(
+            poll_next(Poll::Ready(Some(Err("failed".to_string())))),
+            Poll::Ready(Some(Err("failed".to_string())))
+        );
+    });
+
+    assert_eq!(calls.len(), 4);
+}
+
+macro_rules! bail {
+    ($err:expr) => {
+        return Err($err.to_string())
+    };
+}
+
+#[test]
+fn return_in_macro() {
+    #[slow_function_warning(1ms, clock = mock, {*warned = true;})]
+    pub fn check(value: u32, warned: &mut bool) -> Result<u32, String> {
+        MockClock::advance(Duration::from_millis(2));
+        if value == 0 {
+            bail!("zero");
+        }
+        Ok(value)
+    }
+
+    let mut warned = false;
+    assert!(check(0, &mut warned).is_err());
+    assert!(warned);
+
+    let calls = capture(|| {
+        assert!(check(0, &mut warned).is_err());
+        assert_eq!(check(1, &mut warned), Ok(1));
+    });
+
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].outcome, Some(Outcome::Err));
+}
+
+#[tokio::test]
+async fn return_in_macro_async() {
+    #[slow_function_warning(1ms, clock = mock, {*warned = true;})]
+    async fn check(value: u32, warned: &mut bool) -> Result<u32, String> {
+        tokio::task::yield_now().await;
+        MockClock::advance(Duration::from_millis(2));
+        if value == 0 {
+            bail!("zero");
+        }
+        Ok(value)
+    }
+
+    let mut warned = false;
+    assert!(check(0, &mut warned).await.is_err());
+
+    assert!(warned);
+}
+
+#[test]
+fn return_in_closure() {
+    #[slow_function_warning(1ms, clock = mock)]
+    pub fn find(values: &[u32]) -> Option<u32> {
+        let is_even = |value: &u32| {
+            if value.is_multiple_of(2) {
+                return true;
+            }
+            false
+        };
+        values.iter().copied().find(is_even)
+    }
+
+    assert_eq!(find(&[1, 2, 3]), Some(2));
+}
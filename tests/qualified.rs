@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+fn sleep(millis: u64) {
+    MockClock::advance(Duration::from_millis(millis));
+}
+
+trait Plugin {
+    fn build(&self) -> u32;
+}
+
+struct Player;
+
+#[slow_function_warning(1ms, clock = mock)]
+impl Player {
+    fn new() -> Self {
+        sleep(2);
+        Player
+    }
+
+    fn update(&self) {
+        sleep(2);
+    }
+}
+
+#[slow_function_warning(1ms, clock = mock)]
+impl Plugin for Player {
+    fn build(&self) -> u32 {
+        sleep(2);
+        1
+    }
+}
+
+struct Enemy;
+
+#[slow_function_warning(1ms, clock = mock)]
+impl Enemy {
+    #[cfg_attr(all(), slow_function_warning(5ms, clock = mock))]
+    fn update(&self) {
+        sleep(2);
+    }
+
+    #[assert_fast(5ms, clock = mock)]
+    fn think(&self) {
+        sleep(2);
+    }
+}
+
+struct Physics<T>(T);
+
+impl<T> Physics<T> {
+    #[slow_function_warning(1ms, clock = mock)]
+    fn update(&mut self) {
+        sleep(2);
+    }
+}
+
+#[test]
+fn free_function() {
+    #[slow_function_warning(1ms, clock = mock)]
+    fn update() {
+        sleep(2);
+    }
+
+    let calls = capture(update);
+
+    assert_eq!(calls[0].qualified_function, "update");
+}
+
+#[test]
+fn method() {
+    let calls = capture(|| Player.update());
+
+    assert_eq!(calls[0].function, "update");
+    assert_eq!(calls[0].qualified_function, "Player::update");
+}
+
+#[test]
+fn associated_function() {
+    let calls = capture(|| {
+        Player::new();
+    });
+
+    assert_eq!(calls[0].qualified_function, "Player::new");
+}
+
+#[test]
+fn trait_method() {
+    let calls = capture(|| {
+        assert_eq!(Player.build(), 1);
+    });
+
+    assert_eq!(calls[0].function, "build");
+    assert_eq!(calls[0].qualified_function, "<Player as Plugin>::build");
+}
+
+#[test]
+fn generic_self_type() {
+    let calls = capture(|| Physics(vec![1u32]).update());
+
+    assert_eq!(calls[0].qualified_function, "Physics<Vec<u32>>::update");
+}
+
+#[test]
+fn qualified_function_variable() {
+    struct Resource;
+
+    impl Resource {
+        #[slow_function_warning(1ms, clock = mock, {*value = qualified_function.clone();})]
+        fn update(&self, value: &mut String) {
+            sleep(2);
+        }
+    }
+
+    let mut value = String::new();
+    Resource.update(&mut value);
+
+    assert_eq!(value, "Resource::update");
+}
+
+#[test]
+fn short_type_names() {
+    assert_eq!(
+        __private::short_type_name("alloc::vec::Vec<my_game::Player>"),
+        "Vec<Player>"
+    );
+    assert_eq!(
+        __private::short_type_name("(core::option::Option<&str>, [my_game::Item; 2])"),
+        "(Option<&str>, [Item; 2])"
+    );
+}
+
+#[test]
+fn method_attribute_overrides_impl() {
+    let calls = capture(|| {
+        Enemy.update();
+        Enemy.think();
+    });
+
+    assert!(calls.is_empty(), "{calls:?}");
+}
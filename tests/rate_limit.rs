@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+#[test]
+fn max_per_sec() {
+    #[slow_function_warning(1ms, clock = mock, max_per_sec = 1, {*warnings += 1;})]
+    pub fn sleep(millis: u64, warnings: &mut u32) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut warnings = 0;
+    for _ in 0..3 {
+        sleep(2, &mut warnings);
+    }
+
+    assert_eq!(warnings, 1);
+}
+
+#[test]
+fn first() {
+    #[slow_function_warning(1ms, clock = mock, first = 2, {*warnings += 1;})]
+    pub fn sleep(millis: u64, warnings: &mut u32) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut warnings = 0;
+    for _ in 0..4 {
+        sleep(2, &mut warnings);
+    }
+
+    assert_eq!(warnings, 2);
+}
+
+#[test]
+fn first_then_every() {
+    #[slow_function_warning(1ms, clock = mock, first = 1, every = 2, {warnings.push(suppressed);})]
+    pub fn sleep(millis: u64, warnings: &mut Vec<u64>) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut warnings = vec![];
+    for _ in 0..5 {
+        sleep(2, &mut warnings);
+    }
+
+    assert_eq!(warnings, vec![0, 1, 1]);
+}
+
+#[test]
+fn suppressed_without_limit() {
+    #[slow_function_warning(1ms, clock = mock, {*value = suppressed;})]
+    pub fn sleep(millis: u64, value: &mut u64) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut value = u64::MAX;
+    sleep(2, &mut value);
+
+    assert_eq!(value, 0);
+}
@@ -0,0 +1,161 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+const FRAME_BUDGET: Duration = Duration::from_micros(16_600);
+
+mod config {
+    use std::time::Duration;
+
+    pub const ERROR_BUDGET: Duration = Duration::from_millis(50);
+}
+
+fn sleep(millis: u64) {
+    MockClock::advance(Duration::from_millis(millis));
+}
+
+#[slow_function_warning(limit = FRAME_BUDGET, clock = mock)]
+fn update(millis: u64) {
+    sleep(millis);
+}
+
+#[test]
+fn constant() {
+    let calls = capture(|| {
+        update(16);
+        update(17);
+    });
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].limit, FRAME_BUDGET);
+}
+
+#[test]
+fn positional_constant() {
+    #[slow_function_warning(FRAME_BUDGET, clock = mock)]
+    fn update(millis: u64) {
+        sleep(millis);
+    }
+
+    let calls = capture(|| {
+        update(16);
+        update(17);
+    });
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].limit, FRAME_BUDGET);
+}
+
+#[test]
+fn named_tier_then_call_statement() {
+    fn handler(elapsed: Duration, value: &mut Option<Duration>) {
+        *value = Some(elapsed);
+    }
+
+    #[slow_function_warning(error = 10ms, clock = mock, handler(elapsed, value))]
+    fn update(millis: u64, value: &mut Option<Duration>) {
+        sleep(millis);
+    }
+
+    let mut value = None;
+    update(5, &mut value);
+    assert_eq!(value, None);
+    update(11, &mut value);
+
+    assert_eq!(value, Some(Duration::from_millis(11)));
+}
+
+#[test]
+fn limit_str() {
+    #[slow_function_warning(limit = FRAME_BUDGET, clock = mock, {*value = format!("{elapsed_str} {limit_str}");})]
+    fn update(value: &mut String) {
+        sleep(20);
+    }
+
+    let mut value = String::new();
+    update(&mut value);
+
+    assert_eq!(value, "20ms 16.6ms");
+}
+
+struct System {
+    budget_us: u64,
+}
+
+impl System {
+    #[slow_function_warning(limit = Duration::from_micros(self.budget_us), clock = mock)]
+    fn run(&mut self, millis: u64) {
+        self.budget_us = 0;
+        sleep(millis);
+    }
+}
+
+#[test]
+fn expression_evaluated_at_call() {
+    let mut system = System { budget_us: 5_000 };
+    let calls = capture(|| {
+        system.run(4);
+        system.budget_us = 5_000;
+        system.run(6);
+    });
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].limit, Duration::from_millis(5));
+}
+
+#[test]
+fn tiers() {
+    #[slow_function_warning(1ms, error = config::ERROR_BUDGET, fatal = (budget * 2, {}), clock = mock)]
+    fn update(millis: u64, budget: Duration) {
+        sleep(millis);
+    }
+
+    let calls = capture(|| {
+        update(2, Duration::from_secs(1));
+        update(60, Duration::from_secs(1));
+        update(60, Duration::from_millis(20));
+    });
+
+    assert_eq!(calls.len(), 3);
+    assert_eq!(calls[0].severity, Severity::Warn);
+    assert_eq!(calls[1].severity, Severity::Error);
+    assert_eq!(calls[1].limit, config::ERROR_BUDGET);
+    assert_eq!(calls[2].severity, Severity::Fatal);
+    assert_eq!(calls[2].limit, Duration::from_millis(40));
+}
+
+#[test]
+fn guard_strategy() {
+    #[slow_function_warning(limit = budget, clock = mock, strategy = guard)]
+    fn update(millis: u64, budget: Duration) {
+        sleep(millis);
+    }
+
+    let calls = capture(|| {
+        update(2, Duration::from_millis(1));
+        update(2, Duration::from_millis(3));
+    });
+
+    assert_eq!(calls.len(), 1);
+}
+
+#[test]
+fn block() {
+    let budget = Duration::from_millis(3);
+    let calls = capture(|| {
+        slow_block!("load", limit = budget, clock = mock, sleep(2));
+    });
+
+    assert!(calls.is_empty());
+}
+
+#[test]
+fn positional_block() {
+    let budget = Duration::from_millis(1);
+    let calls = capture(|| {
+        slow_block!(budget, "load", clock = mock, sleep(2));
+    });
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].limit, budget);
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+#[test]
+fn sample_counter() {
+    #[slow_function_warning(1ms, clock = mock, sample = 1/3, {*warnings += 1;})]
+    pub fn sleep(millis: u64, warnings: &mut u32) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut warnings = 0;
+    for _ in 0..6 {
+        sleep(2, &mut warnings);
+    }
+
+    assert_eq!(warnings, 2);
+}
+
+#[test]
+fn sample_random() {
+    #[slow_function_warning(1ms, clock = mock, sample = 1/2, sampling = random, {*warnings += 1;})]
+    pub fn sleep(millis: u64, warnings: &mut u32) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut warnings = 0;
+    for _ in 0..20 {
+        sleep(1, &mut warnings);
+    }
+
+    assert!(warnings < 20);
+}
+
+#[test]
+fn sample_rate() {
+    #[slow_function_warning(1ms, clock = mock, sample = 1/4, {*value = (sample_rate, sample_weight);})]
+    pub fn sleep(millis: u64, value: &mut (f64, f64)) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut value = (0.0, 0.0);
+    sleep(2, &mut value);
+
+    assert_eq!(value, (0.25, 4.0));
+}
+
+#[test]
+fn sample_returns_value() {
+    #[slow_function_warning(1ms, clock = mock, sample = 1/2)]
+    pub fn double(value: u32) -> u32 {
+        value * 2
+    }
+
+    assert_eq!(double(1), 2);
+    assert_eq!(double(2), 4);
+}
+
+#[tokio::test]
+async fn sample_async() {
+    #[slow_function_warning(1ms, clock = mock, sample = 1/2, {*warnings += 1;})]
+    pub async fn sleep(millis: u64, warnings: &mut u32) {
+        tokio::task::yield_now().await;
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut warnings = 0;
+    for _ in 0..4 {
+        sleep(2, &mut warnings).await;
+    }
+
+    assert_eq!(warnings, 2);
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+fn sleep(millis: u64) {
+    MockClock::advance(Duration::from_millis(millis));
+}
+
+#[slow_function_warning(1ms, clock = mock)]
+fn leaf(millis: u64) {
+    sleep(millis);
+}
+
+#[slow_function_warning(1ms, clock = mock)]
+fn parent() {
+    sleep(1);
+    leaf(3);
+    leaf(2);
+}
+
+#[slow_function_warning(1ms, clock = mock, measure = self)]
+fn exclusive_parent(millis: u64) {
+    sleep(millis);
+    leaf(3);
+}
+
+#[slow_function_warning(1ms, clock = mock, strategy = guard)]
+fn guard_parent() {
+    sleep(1);
+    leaf(3);
+}
+
+#[test]
+fn self_elapsed() {
+    let calls = capture(parent);
+
+    assert_eq!(calls.len(), 3);
+    assert_eq!(calls[2].function, "parent");
+    assert_eq!(calls[2].elapsed, Duration::from_millis(6));
+    assert_eq!(calls[2].self_elapsed, Duration::from_millis(1));
+    assert_eq!(calls[0].self_elapsed, Duration::from_millis(3));
+}
+
+#[test]
+fn measure_self() {
+    let calls = capture(|| exclusive_parent(1));
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].function, "leaf");
+
+    let calls = capture(|| exclusive_parent(2));
+
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[1].function, "exclusive_parent");
+    assert_eq!(calls[1].self_elapsed, Duration::from_millis(2));
+}
+
+#[test]
+fn guard_strategy() {
+    let calls = capture(guard_parent);
+
+    assert_eq!(calls[1].function, "guard_parent");
+    assert_eq!(calls[1].self_elapsed, Duration::from_millis(1));
+}
+
+#[test]
+fn early_return() {
+    #[slow_function_warning(1ms, clock = mock)]
+    fn find(values: &[u64]) -> Option<u64> {
+        for &value in values {
+            leaf(value);
+            if value == 3 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    let calls = capture(|| {
+        find(&[2, 3]);
+        parent();
+    });
+
+    assert_eq!(calls[2].function, "find");
+    assert_eq!(calls[2].self_elapsed, Duration::ZERO);
+    assert_eq!(calls[5].function, "parent");
+    assert_eq!(calls[5].self_elapsed, Duration::from_millis(1));
+}
+
+#[test]
+fn panic_unwinds_stack() {
+    #[slow_function_warning(1ms, clock = mock)]
+    fn panics(fail: bool) {
+        leaf(2);
+        assert!(!fail, "failed");
+    }
+
+    assert!(std::panic::catch_unwind(|| panics(true)).is_err());
+    let calls = capture(parent);
+
+    assert_eq!(calls[2].self_elapsed, Duration::from_millis(1));
+}
+
+#[test]
+fn self_elapsed_variable() {
+    #[slow_function_warning(1ms, clock = mock, {*value = self_elapsed_str.clone();})]
+    fn update(value: &mut String) {
+        leaf(2);
+        sleep(1);
+    }
+
+    let mut value = String::new();
+    update(&mut value);
+
+    assert_eq!(value, "1ms");
+}
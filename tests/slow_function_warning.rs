@@ -1,12 +1,12 @@
-use std::{thread, time::Duration};
+use std::time::Duration;
 
 use slow_function_warning::*;
 
 #[test]
 fn warn() {
-    #[slow_function_warning(1ms, {*warned = true;})]
+    #[slow_function_warning(1ms, clock = mock, {*warned = true;})]
     pub fn sleep(millis: u64, warned: &mut bool) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut warned = false;
@@ -17,9 +17,9 @@ fn warn() {
 
 #[test]
 fn no_warn() {
-    #[slow_function_warning(10ms, {*warned = true;})]
+    #[slow_function_warning(10ms, clock = mock, {*warned = true;})]
     pub fn sleep(millis: u64, warned: &mut bool) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut warned = false;
@@ -30,12 +30,12 @@ fn no_warn() {
 
 #[test]
 fn warn_using_params() {
-    #[slow_function_warning(1ms, {
+    #[slow_function_warning(1ms, clock = mock, {
         println!("{module}::{function} {param}");
         *warned = true;
     })]
     pub fn sleep(millis: u64, param: &str, warned: &mut bool) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut warned = false;
@@ -46,12 +46,12 @@ fn warn_using_params() {
 
 #[test]
 fn no_warn_using_params() {
-    #[slow_function_warning(10ms, {
+    #[slow_function_warning(10ms, clock = mock, {
         println!("{module}::{function} {param}");
         *warned = true;
     })]
     pub fn sleep(millis: u64, param: &str, warned: &mut bool) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut warned = false;
@@ -67,12 +67,12 @@ fn warn_impl() {
     }
 
     impl MyStruct {
-        #[slow_function_warning(1ms, {
+        #[slow_function_warning(1ms, clock = mock, {
             println!("{module}::{function} {param}");
             self.warned = true;
         })]
         pub fn sleep(&mut self, millis: u64, param: &str) {
-            thread::sleep(Duration::from_millis(millis));
+            MockClock::advance(Duration::from_millis(millis));
         }
     }
 
@@ -89,12 +89,12 @@ fn no_warn_impl() {
     }
 
     impl MyStruct {
-        #[slow_function_warning(10ms, {
+        #[slow_function_warning(10ms, clock = mock, {
             println!("{module}::{function} {param}");
             self.warned = true;
         })]
         pub fn sleep(&mut self, millis: u64, param: &str) {
-            thread::sleep(Duration::from_millis(millis));
+            MockClock::advance(Duration::from_millis(millis));
         }
     }
 
@@ -106,12 +106,13 @@ fn no_warn_impl() {
 
 #[tokio::test]
 async fn warn_async() {
-    #[slow_function_warning(1ms, {
+    #[slow_function_warning(1ms, clock = mock, {
         println!("{module}::{function} {param}");
         *warned = true;
     })]
     pub async fn sleep(millis: u64, param: &str, warned: &mut bool) {
-        tokio::time::sleep(Duration::from_millis(millis)).await;
+        tokio::task::yield_now().await;
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut warned = false;
@@ -122,12 +123,13 @@ async fn warn_async() {
 
 #[tokio::test]
 async fn no_warn_async() {
-    #[slow_function_warning(50ms, {
+    #[slow_function_warning(50ms, clock = mock, {
         println!("{module}::{function} {param}");
         *warned = true;
     })]
     pub async fn sleep(millis: u64, param: &str, warned: &mut bool) {
-        tokio::time::sleep(Duration::from_millis(millis)).await;
+        tokio::task::yield_now().await;
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut warned = false;
@@ -0,0 +1,239 @@
+use std::{fmt::Display, time::Duration};
+
+use slow_function_warning::*;
+
+fn sleep(millis: u64) {
+    MockClock::advance(Duration::from_millis(millis));
+}
+
+#[test]
+fn const_generic() {
+    #[slow_function_warning(1ms, clock = mock)]
+    fn inline<const N: usize>() -> [u8; N] {
+        sleep(2);
+        [0; N]
+    }
+
+    #[slow_function_warning(1ms, clock = mock, strategy = guard)]
+    fn guard<const N: usize>() -> [u8; N] {
+        sleep(2);
+        [0; N]
+    }
+
+    let calls = capture(|| {
+        assert_eq!(inline::<3>(), [0; 3]);
+        assert_eq!(guard::<3>(), [0; 3]);
+    });
+
+    assert_eq!(calls.len(), 2);
+}
+
+#[test]
+fn lifetime_generic() {
+    #[slow_function_warning(1ms, clock = mock)]
+    fn inline<'a>(first: &'a str, second: &'a str) -> &'a str {
+        sleep(2);
+        if first.len() > second.len() {
+            return first;
+        }
+        second
+    }
+
+    #[slow_function_warning(1ms, clock = mock, strategy = guard)]
+    fn guard<'a>(first: &'a str, second: &'a str) -> &'a str {
+        sleep(2);
+        if first.len() > second.len() {
+            return first;
+        }
+        second
+    }
+
+    let calls = capture(|| {
+        assert_eq!(inline("long", "s"), "long");
+        assert_eq!(guard("long", "s"), "long");
+    });
+
+    assert_eq!(calls.len(), 2);
+}
+
+#[test]
+fn impl_trait() {
+    #[slow_function_warning(1ms, clock = mock)]
+    fn inline(value: u32) -> impl Display {
+        sleep(2);
+        if value > 10 {
+            return format!("big {value}");
+        }
+        format!("small {value}")
+    }
+
+    #[slow_function_warning(1ms, clock = mock, strategy = guard)]
+    fn guard(value: u32) -> impl Display {
+        sleep(2);
+        if value > 10 {
+            return format!("big {value}");
+        }
+        format!("small {value}")
+    }
+
+    let calls = capture(|| {
+        assert_eq!(inline(20).to_string(), "big 20");
+        assert_eq!(guard(1).to_string(), "small 1");
+    });
+
+    assert_eq!(calls.len(), 2);
+}
+
+#[test]
+fn return_coercion() {
+    #[slow_function_warning(1ms, clock = mock)]
+    fn inline(value: u32) -> Box<dyn Display> {
+        sleep(2);
+        if value > 10 {
+            return Box::new(value);
+        }
+        Box::new("small")
+    }
+
+    #[slow_function_warning(1ms, clock = mock, strategy = guard)]
+    fn guard(value: u32) -> Box<dyn Display> {
+        sleep(2);
+        if value > 10 {
+            return Box::new(value);
+        }
+        Box::new("small")
+    }
+
+    let calls = capture(|| {
+        assert_eq!(inline(20).to_string(), "20");
+        assert_eq!(guard(1).to_string(), "small");
+    });
+
+    assert_eq!(calls.len(), 2);
+}
+
+#[test]
+fn unsafe_fn() {
+    #[slow_function_warning(1ms, clock = mock)]
+    unsafe fn inline(value: *const u32) -> u32 {
+        sleep(2);
+        *value
+    }
+
+    #[slow_function_warning(1ms, clock = mock, strategy = guard)]
+    unsafe fn guard(value: *const u32) -> u32 {
+        sleep(2);
+        *value
+    }
+
+    let value = 7;
+    let calls = capture(|| unsafe {
+        assert_eq!(inline(&value), 7);
+        assert_eq!(guard(&value), 7);
+    });
+
+    assert_eq!(calls.len(), 2);
+}
+
+#[test]
+fn extern_c() {
+    #[slow_function_warning(1ms, clock = mock)]
+    extern "C" fn inline(value: u32) -> u32 {
+        sleep(2);
+        value * 2
+    }
+
+    #[slow_function_warning(1ms, clock = mock, strategy = guard)]
+    extern "C" fn guard(value: u32) -> u32 {
+        sleep(2);
+        value * 2
+    }
+
+    let calls = capture(|| {
+        assert_eq!(inline(2), 4);
+        assert_eq!(guard(2), 4);
+    });
+
+    assert_eq!(calls.len(), 2);
+}
+
+#[test]
+fn guard_question_mark_in_macro() {
+    macro_rules! parse {
+        ($value:expr) => {
+            $value.parse::<u32>()?
+        };
+    }
+
+    #[slow_function_warning(1ms, clock = mock, strategy = guard)]
+    fn guard(value: &str) -> Result<u32, std::num::ParseIntError> {
+        sleep(2);
+        Ok(parse!(value))
+    }
+
+    let calls = capture(|| {
+        assert!(guard("nope").is_err());
+    });
+
+    assert_eq!(calls.len(), 1);
+}
+
+#[test]
+fn guard_parameters_used_by_body() {
+    struct Counter {
+        count: u32,
+    }
+
+    impl Counter {
+        #[slow_function_warning(1ms, clock = mock, strategy = guard)]
+        fn increment(&mut self) -> u32 {
+            sleep(2);
+            self.count += 1;
+            self.count
+        }
+    }
+
+    #[slow_function_warning(1ms, clock = mock, strategy = guard, capture(values.len()))]
+    fn guard(mut values: Vec<u32>) -> Vec<u32> {
+        sleep(2);
+        values.sort();
+        values
+    }
+
+    let mut counter = Counter { count: 0 };
+    let calls = capture(|| {
+        assert_eq!(counter.increment(), 1);
+        assert_eq!(guard(vec![3, 1, 2]), vec![1, 2, 3]);
+    });
+
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[1].args, vec![("values.len()", "3".to_string())]);
+}
+
+#[test]
+fn guard_no_warn() {
+    #[slow_function_warning(10ms, clock = mock, strategy = guard)]
+    fn guard() {
+        sleep(2);
+    }
+
+    let calls = capture(guard);
+
+    assert!(calls.is_empty());
+}
+
+#[test]
+fn guard_async() {
+    #[slow_function_warning(1ms, clock = mock, strategy = guard)]
+    async fn guard(millis: u64) {
+        tokio::task::yield_now().await;
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let calls = capture(|| runtime.block_on(guard(2)));
+
+    assert_eq!(calls.len(), 1);
+}
@@ -0,0 +1,85 @@
+use std::{
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use slow_function_warning::*;
+
+#[slow_function_warning(1ms, clock = mock)]
+fn sleep(millis: u64) {
+    MockClock::advance(Duration::from_millis(millis));
+}
+
+#[test]
+fn thread_name() {
+    let calls = thread::Builder::new()
+        .name("render".to_string())
+        .spawn(|| capture(|| sleep(2)))
+        .unwrap()
+        .join()
+        .unwrap();
+
+    assert_eq!(calls[0].thread_name.as_deref(), Some("render"));
+}
+
+#[test]
+fn thread_id() {
+    let handle = thread::spawn(|| capture(|| sleep(2)));
+    let id = handle.thread().id();
+    let calls = handle.join().unwrap();
+
+    assert_eq!(calls[0].thread_id, id);
+    assert_ne!(calls[0].thread_id, thread::current().id());
+}
+
+#[test]
+fn timestamp() {
+    let before = SystemTime::now();
+    let calls = capture(|| sleep(2));
+    let after = SystemTime::now();
+
+    // The mock clock doesn't advance the wall clock, so the call appears to
+    // start 2ms before it was made.
+    assert!(calls[0].timestamp <= after - Duration::from_millis(2));
+    assert!(calls[0].timestamp >= before - Duration::from_millis(2));
+}
+
+#[test]
+fn variables() {
+    #[slow_function_warning(1ms, clock = mock, {*value = (thread_name.clone(), thread_id, timestamp_str.clone());})]
+    fn update(value: &mut (Option<String>, thread::ThreadId, String)) {
+        MockClock::advance(Duration::from_millis(2));
+    }
+
+    let mut value = (None, thread::current().id(), String::new());
+    thread::Builder::new()
+        .name("audio".to_string())
+        .spawn(move || {
+            update(&mut value);
+            assert_eq!(value.0.as_deref(), Some("audio"));
+            assert_eq!(value.1, thread::current().id());
+            assert_eq!(value.2.len(), "2024-05-01T12:30:05.123Z".len());
+            assert!(value.2.ends_with('Z'));
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn format_timestamp() {
+    use slow_function_warning::__private::format_timestamp;
+
+    assert_eq!(
+        format_timestamp(SystemTime::UNIX_EPOCH),
+        "1970-01-01T00:00:00.000Z"
+    );
+    assert_eq!(
+        format_timestamp(SystemTime::UNIX_EPOCH + Duration::from_millis(951_827_696_789)),
+        "2000-02-29T12:34:56.789Z"
+    );
+    assert_eq!(
+        format_timestamp(SystemTime::UNIX_EPOCH + Duration::from_secs(1_735_689_599)),
+        "2024-12-31T23:59:59.000Z"
+    );
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use slow_function_warning::*;
+
+#[slow_function_warning(warn = 1ms, error = 10ms, fatal = 1s, clock = mock, {})]
+fn sleep(millis: u64) {
+    MockClock::advance(Duration::from_millis(millis));
+}
+
+#[test]
+fn severity() {
+    let calls = capture(|| {
+        sleep(1);
+        sleep(2);
+        sleep(20);
+        sleep(2000);
+    });
+
+    assert_eq!(calls.len(), 3);
+    assert_eq!(calls[0].severity, Severity::Warn);
+    assert_eq!(calls[0].limit, Duration::from_millis(1));
+    assert_eq!(calls[1].severity, Severity::Error);
+    assert_eq!(calls[1].limit, Duration::from_millis(10));
+    assert_eq!(calls[2].severity, Severity::Fatal);
+    assert_eq!(calls[2].limit, Duration::from_secs(1));
+}
+
+#[test]
+fn tier_statements() {
+    #[slow_function_warning(
+        1ms,
+        {*value = format!("{severity} {limit_str}");},
+        error = (10ms, {*value = format!("error {ratio}");}),
+        clock = mock,
+    )]
+    fn update(millis: u64, value: &mut String) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut value = String::new();
+    update(2, &mut value);
+    assert_eq!(value, "Warning 1ms");
+
+    update(25, &mut value);
+    assert_eq!(value, "error 2.5");
+}
+
+#[test]
+fn positional_time_is_warn() {
+    #[slow_function_warning(5ms, error = 10ms, clock = mock, {*value = severity;})]
+    fn update(millis: u64, value: &mut Severity) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut value = Severity::Fatal;
+    update(6, &mut value);
+    assert_eq!(value, Severity::Warn);
+
+    update(11, &mut value);
+    assert_eq!(value, Severity::Error);
+}
+
+#[test]
+fn only_higher_tiers() {
+    #[slow_function_warning(error = 10ms, clock = mock, {*value = Some((severity, ratio));})]
+    fn update(millis: u64, value: &mut Option<(Severity, f64)>) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut value = None;
+    update(5, &mut value);
+    assert_eq!(value, None);
+
+    update(15, &mut value);
+    assert_eq!(value, Some((Severity::Error, 1.5)));
+}
+
+#[test]
+fn default_error_message() {
+    #[slow_function_warning(error = 1ms, clock = mock)]
+    fn update() {
+        MockClock::advance(Duration::from_millis(2));
+    }
+
+    update();
+}
+
+#[test]
+#[should_panic(expected = "Fatal: tiers::update: ran for 2ms (limit: 1ms)")]
+fn default_fatal_message_panics() {
+    #[slow_function_warning(fatal = 1ms, clock = mock)]
+    fn update() {
+        MockClock::advance(Duration::from_millis(2));
+    }
+
+    update();
+}
+
+#[test]
+fn severity_order() {
+    assert!(Severity::Warn < Severity::Error);
+    assert!(Severity::Error < Severity::Fatal);
+    assert_eq!(Severity::Error.to_string(), "Error");
+}
@@ -1,12 +1,12 @@
-use std::{thread, time::Duration};
+use std::time::Duration;
 
 use slow_function_warning::*;
 
 #[test]
 fn module() {
-    #[slow_function_warning(1ms, {*value = module.to_string();})]
+    #[slow_function_warning(1ms, clock = mock, {*value = module.to_string();})]
     pub fn sleep(millis: u64, value: &mut String) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = "".to_string();
@@ -17,9 +17,9 @@ fn module() {
 
 #[test]
 fn function() {
-    #[slow_function_warning(1ms, {*value = function.to_string();})]
+    #[slow_function_warning(1ms, clock = mock, {*value = function.to_string();})]
     pub fn sleep(millis: u64, value: &mut String) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = "".to_string();
@@ -30,9 +30,9 @@ fn function() {
 
 #[test]
 fn elapsed() {
-    #[slow_function_warning(1ms, {*duration = elapsed;})]
+    #[slow_function_warning(1ms, clock = mock, {*duration = elapsed;})]
     pub fn sleep(millis: u64, duration: &mut Duration) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut duration = Duration::default();
@@ -43,9 +43,9 @@ fn elapsed() {
 
 #[test]
 fn elapsed_str() {
-    #[slow_function_warning(1ms, {*value = elapsed_str.clone();})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_str.clone();})]
     pub fn sleep(millis: u64, value: &mut String) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = "".to_string();
@@ -56,87 +56,87 @@ fn elapsed_str() {
 
 #[test]
 fn elapsed_ns() {
-    #[slow_function_warning(1ms, {*value = elapsed_ns;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_ns;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
     sleep(2, &mut value);
 
-    assert!(value > 2000000);
+    assert_eq!(value, 2000000);
 }
 
 #[test]
 fn elapsed_nanos() {
-    #[slow_function_warning(1ms, {*value = elapsed_nanos;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_nanos;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
     sleep(2, &mut value);
 
-    assert!(value > 2000000);
+    assert_eq!(value, 2000000);
 }
 
 #[test]
 fn elapsed_nanoseconds() {
-    #[slow_function_warning(1ms, {*value = elapsed_nanoseconds;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_nanoseconds;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
     sleep(2, &mut value);
 
-    assert!(value > 2000000);
+    assert_eq!(value, 2000000);
 }
 
 #[test]
 fn elapsed_us() {
-    #[slow_function_warning(1ms, {*value = elapsed_us;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_us;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
     sleep(2, &mut value);
 
-    assert!(value > 2000);
+    assert_eq!(value, 2000);
 }
 
 #[test]
 fn elapsed_micros() {
-    #[slow_function_warning(1ms, {*value = elapsed_micros;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_micros;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
     sleep(2, &mut value);
 
-    assert!(value > 2000);
+    assert_eq!(value, 2000);
 }
 
 #[test]
 fn elapsed_microseconds() {
-    #[slow_function_warning(1ms, {*value = elapsed_microseconds;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_microseconds;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
     sleep(2, &mut value);
 
-    assert!(value > 2000);
+    assert_eq!(value, 2000);
 }
 
 #[test]
 fn elapsed_ms() {
-    #[slow_function_warning(1ms, {*value = elapsed_ms;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_ms;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
@@ -147,9 +147,9 @@ fn elapsed_ms() {
 
 #[test]
 fn elapsed_millis() {
-    #[slow_function_warning(1ms, {*value = elapsed_millis;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_millis;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
@@ -160,9 +160,9 @@ fn elapsed_millis() {
 
 #[test]
 fn elapsed_milliseconds() {
-    #[slow_function_warning(1ms, {*value = elapsed_milliseconds;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_milliseconds;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
@@ -173,9 +173,9 @@ fn elapsed_milliseconds() {
 
 #[test]
 fn elapsed_s() {
-    #[slow_function_warning(1ms, {*value = elapsed_s;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_s;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -186,9 +186,9 @@ fn elapsed_s() {
 
 #[test]
 fn elapsed_secs() {
-    #[slow_function_warning(1ms, {*value = elapsed_secs;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_secs;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -199,9 +199,9 @@ fn elapsed_secs() {
 
 #[test]
 fn elapsed_seconds() {
-    #[slow_function_warning(1ms, {*value = elapsed_seconds;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_seconds;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -212,9 +212,9 @@ fn elapsed_seconds() {
 
 #[test]
 fn elapsed_m() {
-    #[slow_function_warning(1ms, {*value = elapsed_m;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_m;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -225,9 +225,9 @@ fn elapsed_m() {
 
 #[test]
 fn elapsed_min() {
-    #[slow_function_warning(1ms, {*value = elapsed_min;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_min;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -238,9 +238,9 @@ fn elapsed_min() {
 
 #[test]
 fn elapsed_minutes() {
-    #[slow_function_warning(1ms, {*value = elapsed_minutes;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_minutes;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -251,9 +251,9 @@ fn elapsed_minutes() {
 
 #[test]
 fn elapsed_h() {
-    #[slow_function_warning(1ms, {*value = elapsed_h;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_h;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -264,9 +264,9 @@ fn elapsed_h() {
 
 #[test]
 fn elapsed_hours() {
-    #[slow_function_warning(1ms, {*value = elapsed_hours;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_hours;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -277,9 +277,9 @@ fn elapsed_hours() {
 
 #[test]
 fn elapsed_d() {
-    #[slow_function_warning(1ms, {*value = elapsed_d;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_d;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -290,9 +290,9 @@ fn elapsed_d() {
 
 #[test]
 fn elapsed_days() {
-    #[slow_function_warning(1ms, {*value = elapsed_days;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = elapsed_days;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -303,9 +303,9 @@ fn elapsed_days() {
 
 #[test]
 fn limit() {
-    #[slow_function_warning(1ms, {*duration = limit;})]
+    #[slow_function_warning(1ms, clock = mock, {*duration = limit;})]
     pub fn sleep(millis: u64, duration: &mut Duration) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut duration = Duration::default();
@@ -316,9 +316,9 @@ fn limit() {
 
 #[test]
 fn limit_str() {
-    #[slow_function_warning(1ms, {*value = limit_str.clone();})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_str.clone();})]
     pub fn sleep(millis: u64, value: &mut String) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = "".to_string();
@@ -329,9 +329,9 @@ fn limit_str() {
 
 #[test]
 fn limit_ns() {
-    #[slow_function_warning(1ms, {*value = limit_ns;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_ns;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
@@ -342,9 +342,9 @@ fn limit_ns() {
 
 #[test]
 fn limit_nanos() {
-    #[slow_function_warning(1ms, {*value = limit_nanos;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_nanos;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
@@ -355,9 +355,9 @@ fn limit_nanos() {
 
 #[test]
 fn limit_nanoseconds() {
-    #[slow_function_warning(1ms, {*value = limit_nanoseconds;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_nanoseconds;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
@@ -368,9 +368,9 @@ fn limit_nanoseconds() {
 
 #[test]
 fn limit_us() {
-    #[slow_function_warning(1ms, {*value = limit_us;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_us;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
@@ -381,9 +381,9 @@ fn limit_us() {
 
 #[test]
 fn limit_micros() {
-    #[slow_function_warning(1ms, {*value = limit_micros;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_micros;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
@@ -394,9 +394,9 @@ fn limit_micros() {
 
 #[test]
 fn limit_microseconds() {
-    #[slow_function_warning(1ms, {*value = limit_microseconds;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_microseconds;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
@@ -407,9 +407,9 @@ fn limit_microseconds() {
 
 #[test]
 fn limit_ms() {
-    #[slow_function_warning(1ms, {*value = limit_ms;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_ms;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
@@ -420,9 +420,9 @@ fn limit_ms() {
 
 #[test]
 fn limit_millis() {
-    #[slow_function_warning(1ms, {*value = limit_millis;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_millis;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
@@ -433,9 +433,9 @@ fn limit_millis() {
 
 #[test]
 fn limit_milliseconds() {
-    #[slow_function_warning(1ms, {*value = limit_milliseconds;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_milliseconds;})]
     pub fn sleep(millis: u64, value: &mut u128) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u128::default();
@@ -446,9 +446,9 @@ fn limit_milliseconds() {
 
 #[test]
 fn limit_s() {
-    #[slow_function_warning(1ms, {*value = limit_s;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_s;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -459,9 +459,9 @@ fn limit_s() {
 
 #[test]
 fn limit_secs() {
-    #[slow_function_warning(1ms, {*value = limit_secs;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_secs;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -472,9 +472,9 @@ fn limit_secs() {
 
 #[test]
 fn limit_seconds() {
-    #[slow_function_warning(1ms, {*value = limit_seconds;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_seconds;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -485,9 +485,9 @@ fn limit_seconds() {
 
 #[test]
 fn limit_m() {
-    #[slow_function_warning(1ms, {*value = limit_m;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_m;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -498,9 +498,9 @@ fn limit_m() {
 
 #[test]
 fn limit_min() {
-    #[slow_function_warning(1ms, {*value = limit_min;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_min;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -511,9 +511,9 @@ fn limit_min() {
 
 #[test]
 fn limit_minutes() {
-    #[slow_function_warning(1ms, {*value = limit_minutes;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_minutes;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -524,9 +524,9 @@ fn limit_minutes() {
 
 #[test]
 fn limit_h() {
-    #[slow_function_warning(1ms, {*value = limit_h;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_h;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -537,9 +537,9 @@ fn limit_h() {
 
 #[test]
 fn limit_hours() {
-    #[slow_function_warning(1ms, {*value = limit_hours;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_hours;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -550,9 +550,9 @@ fn limit_hours() {
 
 #[test]
 fn limit_d() {
-    #[slow_function_warning(1ms, {*value = limit_d;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_d;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -563,9 +563,9 @@ fn limit_d() {
 
 #[test]
 fn limit_days() {
-    #[slow_function_warning(1ms, {*value = limit_days;})]
+    #[slow_function_warning(1ms, clock = mock, {*value = limit_days;})]
     pub fn sleep(millis: u64, value: &mut u64) {
-        thread::sleep(Duration::from_millis(millis));
+        MockClock::advance(Duration::from_millis(millis));
     }
 
     let mut value = u64::default();
@@ -573,3 +573,72 @@ fn limit_days() {
 
     assert_eq!(value, 0);
 }
+
+#[test]
+fn return_value() {
+    #[slow_function_warning(1ms, clock = mock, {*value = return_value.len();})]
+    pub fn sleep(millis: u64, value: &mut usize) -> Vec<u64> {
+        MockClock::advance(Duration::from_millis(millis));
+        vec![millis; 3]
+    }
+
+    let mut value = usize::default();
+    let returned = sleep(2, &mut value);
+
+    assert_eq!(value, 3);
+    assert_eq!(returned, vec![2; 3]);
+}
+
+#[test]
+fn result_param_not_shadowed() {
+    #[slow_function_warning(1ms, clock = mock, {*value = *result;})]
+    pub fn sleep(millis: u64, result: &u64, value: &mut u64) -> u64 {
+        MockClock::advance(Duration::from_millis(millis));
+        millis
+    }
+
+    let mut value = u64::default();
+    sleep(2, &7, &mut value);
+
+    assert_eq!(value, 7);
+}
+
+#[test]
+fn file() {
+    #[slow_function_warning(1ms, clock = mock, {*value = file;})]
+    pub fn sleep(millis: u64, value: &mut &'static str) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut value = "";
+    sleep(2, &mut value);
+
+    assert_eq!(value, file!());
+}
+
+#[test]
+fn line() {
+    let expected = line!() + 2;
+    #[slow_function_warning(1ms, clock = mock, {*value = line;})]
+    pub fn sleep(millis: u64, value: &mut u32) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut value = u32::default();
+    sleep(2, &mut value);
+
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn column() {
+    #[slow_function_warning(1ms, clock = mock, {*value = column;})]
+    pub fn sleep(millis: u64, value: &mut u32) {
+        MockClock::advance(Duration::from_millis(millis));
+    }
+
+    let mut value = u32::default();
+    sleep(2, &mut value);
+
+    assert_eq!(value, 12);
+}